@@ -8,6 +8,7 @@ use std::sync::Arc;
 /// Account data of the currently logged in user.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Account {
@@ -28,6 +29,10 @@ pub struct Account {
 
     #[serde(rename = "mature_content_flag_manga")]
     #[serde(deserialize_with = "mature_content_flag_manga")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "serialize_mature_content_flag_manga")
+    )]
     pub manga_maturity_rating: MaturityRating,
     #[serde(rename = "maturity_rating")]
     pub video_maturity_rating: MaturityRating,
@@ -250,13 +255,27 @@ fn mature_content_flag_manga<'de, D: serde::Deserializer<'de>>(
     })
 }
 
+#[cfg(feature = "serde")]
+fn serialize_mature_content_flag_manga<S: serde::Serializer>(
+    maturity_rating: &MaturityRating,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(match maturity_rating {
+        MaturityRating::NotMature => "0",
+        MaturityRating::Mature => "1",
+        MaturityRating::Custom(custom) => custom,
+    })
+}
+
 mod wallpaper {
     use crate::{Crunchyroll, Request, Result};
     use serde::Deserialize;
 
     /// Wallpaper which are shown at the top of your Crunchyroll profile.
     #[derive(Clone, Debug, Default, Deserialize, Request)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[serde(from = "String")]
+    #[cfg_attr(feature = "serde", serde(into = "String"))]
     #[cfg_attr(not(feature = "__test_strict"), serde(default))]
     pub struct Wallpaper {
         pub name: String,
@@ -276,6 +295,12 @@ mod wallpaper {
         }
     }
 
+    impl From<Wallpaper> for String {
+        fn from(wallpaper: Wallpaper) -> Self {
+            wallpaper.name
+        }
+    }
+
     impl Wallpaper {
         /// Returns all available wallpapers
         pub async fn all_wallpapers(crunchyroll: &Crunchyroll) -> Result<Vec<Wallpaper>> {
@@ -0,0 +1,448 @@
+//! Release calendar of episodes.
+
+use crate::list::{WatchlistEntry, WatchlistOptions};
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
+use crate::media::MediaType;
+use crate::search::{BrowseOptions, SimulcastSeason};
+use crate::{Crunchyroll, Episode, Locale, MediaCollection, Result, Season, Series};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+
+/// If a [`CalendarEntry`] is about an episode becoming available for premium or for free users.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReleaseKind {
+    Premium,
+    Free,
+}
+
+/// A single release of an episode.
+#[derive(Clone, Debug)]
+pub struct CalendarEntry {
+    pub kind: ReleaseKind,
+    /// Date when the episode becomes available. Either [`Episode::premium_available_date`] or
+    /// [`Episode::free_available_date`], depending on [`CalendarEntry::kind`].
+    pub date: DateTime<Utc>,
+
+    pub episode: Episode,
+}
+
+/// All releases of a series in one audio locale.
+#[derive(Clone, Debug)]
+pub struct CalendarGroup {
+    pub series_id: String,
+    pub series_title: String,
+    pub audio_locale: Locale,
+
+    /// Sorted by their release date.
+    pub entries: Vec<CalendarEntry>,
+}
+
+/// Episode releases in a date range. Get it via [`Crunchyroll::simulcast_calendar`] or
+/// [`Crunchyroll::watchlist_calendar`].
+#[derive(Clone, Debug)]
+pub struct ReleaseCalendar {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+
+    /// Sorted by the release date of their first entry.
+    pub groups: Vec<CalendarGroup>,
+
+    /// Ids of series which are missing in the calendar because their seasons or episodes could not
+    /// be requested.
+    pub skipped_series: Vec<String>,
+}
+
+impl ReleaseCalendar {
+    fn from_episodes(
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        episodes: Vec<Episode>,
+        skipped_series: Vec<String>,
+    ) -> Self {
+        let mut groups: Vec<CalendarGroup> = vec![];
+        let mut group_index: HashMap<(String, Locale), usize> = HashMap::new();
+
+        for episode in episodes {
+            let mut releases = vec![(ReleaseKind::Free, episode.free_available_date)];
+            // episodes which are free from the start only have one release
+            if episode.premium_available_date != episode.free_available_date {
+                releases.push((ReleaseKind::Premium, episode.premium_available_date))
+            }
+
+            for (kind, date) in releases {
+                if date < start || date >= end {
+                    continue;
+                }
+                let index = *group_index
+                    .entry((episode.series_id.clone(), episode.audio_locale.clone()))
+                    .or_insert_with(|| {
+                        groups.push(CalendarGroup {
+                            series_id: episode.series_id.clone(),
+                            series_title: episode.series_title.clone(),
+                            audio_locale: episode.audio_locale.clone(),
+                            entries: vec![],
+                        });
+                        groups.len() - 1
+                    });
+                groups[index].entries.push(CalendarEntry {
+                    kind,
+                    date,
+                    episode: episode.clone(),
+                })
+            }
+        }
+
+        for group in groups.iter_mut() {
+            group.entries.sort_by_key(|e| e.date)
+        }
+        groups.sort_by_key(|g| g.entries[0].date);
+
+        Self {
+            start,
+            end,
+            groups,
+            skipped_series,
+        }
+    }
+
+    /// All entries of all groups, sorted by their release date.
+    pub fn entries(&self) -> Vec<&CalendarEntry> {
+        let mut entries: Vec<&CalendarEntry> =
+            self.groups.iter().flat_map(|g| &g.entries).collect();
+        entries.sort_by_key(|e| e.date);
+        entries
+    }
+
+    /// Export the calendar as iCalendar ([RFC 5545](https://www.rfc-editor.org/rfc/rfc5545)) feed
+    /// which can be imported into most calendar applications. Every entry is an event which lasts
+    /// as long as the episode.
+    pub fn to_ics(&self) -> String {
+        let now = format_ics_date(&Utc::now());
+
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//crunchyroll-rs//Release Calendar//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+        ];
+        for entry in self.entries() {
+            let episode = &entry.episode;
+            let kind = match entry.kind {
+                ReleaseKind::Premium => "Premium",
+                ReleaseKind::Free => "Free",
+            };
+            let name = if episode.episode.is_empty() {
+                episode.title.clone()
+            } else {
+                format!("Episode {}", episode.episode)
+            };
+
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}-{}@crunchyroll-rs", episode.id, kind.to_lowercase()),
+                format!("DTSTAMP:{}", now),
+                format!("DTSTART:{}", format_ics_date(&entry.date)),
+                format!(
+                    "DTEND:{}",
+                    format_ics_date(&(entry.date + episode.duration))
+                ),
+                format!(
+                    "SUMMARY:{}",
+                    escape_ics_text(&format!(
+                        "{} - {} ({}, {})",
+                        episode.series_title, name, episode.audio_locale, kind
+                    ))
+                ),
+                format!("DESCRIPTION:{}", escape_ics_text(&episode.title)),
+                format!("CATEGORIES:{}", kind),
+                format!("URL:https://www.crunchyroll.com/watch/{}", episode.id),
+                "END:VEVENT".to_string(),
+            ])
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|l| fold_ics_line(l)).collect()
+    }
+}
+
+fn format_ics_date(date: &DateTime<Utc>) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Lines must not be longer than 75 octets, longer ones are continued in the next line which starts
+/// with a space. Every line ends with CRLF.
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            len = 1
+        }
+        folded.push(c);
+        len += c.len_utf8()
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Weekly release slot of a series in one audio locale.
+#[derive(Clone, Debug)]
+pub struct LineupRelease {
+    pub audio_locale: Locale,
+    pub weekday: Weekday,
+    /// Time (UTC) at which new episodes become available for premium users.
+    pub time: NaiveTime,
+}
+
+/// A series which airs in a [`SimulcastSeason`]. Get it via [`SimulcastSeason::lineup`].
+#[derive(Clone, Debug)]
+pub struct LineupEntry {
+    pub series: Series,
+
+    /// Audio locales of the seasons which air in the simulcast season.
+    pub audio_locales: Vec<Locale>,
+    /// Subtitle locales of the seasons which air in the simulcast season.
+    pub subtitle_locales: Vec<Locale>,
+
+    /// Weekday and time of the latest released episode of every audio locale. Empty if no episode
+    /// was released yet.
+    pub releases: Vec<LineupRelease>,
+}
+
+/// All series which air in a [`SimulcastSeason`]. Get it via [`SimulcastSeason::lineup`].
+#[derive(Clone, Debug)]
+pub struct Lineup {
+    /// Sorted by the title of their series.
+    pub entries: Vec<LineupEntry>,
+
+    /// Ids of series which are missing in the lineup because their seasons or episodes could not
+    /// be requested.
+    pub skipped_series: Vec<String>,
+}
+
+impl SimulcastSeason {
+    /// Return all series which air in this simulcast season with their dub and sub availability
+    /// and release times. A limited number of series are requested concurrently. Series whose
+    /// seasons or episodes can't be requested are listed in [`Lineup::skipped_series`].
+    pub async fn lineup(&self) -> Result<Lineup> {
+        let crunchyroll = Crunchyroll {
+            executor: self.executor.clone(),
+        };
+        let series = simulcast_series(&crunchyroll, self).await?;
+
+        let results: Vec<(String, Result<LineupEntry>)> =
+            stream::iter(series.into_iter().map(|s| async move {
+                let id = s.id.clone();
+                (id, lineup_entry(s, self).await)
+            }))
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut entries = vec![];
+        let mut skipped_series = vec![];
+        for (id, result) in results {
+            match result {
+                Ok(entry) => entries.push(entry),
+                Err(_) => skipped_series.push(id),
+            }
+        }
+        entries.sort_by(|a, b| a.series.title.cmp(&b.series.title));
+
+        Ok(Lineup {
+            entries,
+            skipped_series,
+        })
+    }
+}
+
+async fn lineup_entry(series: Series, simulcast_season: &SimulcastSeason) -> Result<LineupEntry> {
+    let seasons = simulcast_seasons_of(&series, simulcast_season).await?;
+
+    let mut audio_locales = vec![];
+    let mut subtitle_locales = vec![];
+    for season in &seasons {
+        for locale in &season.audio_locales {
+            if !audio_locales.contains(locale) {
+                audio_locales.push(locale.clone())
+            }
+        }
+        for locale in &season.subtitle_locales {
+            if !subtitle_locales.contains(locale) {
+                subtitle_locales.push(locale.clone())
+            }
+        }
+    }
+
+    let now = Utc::now();
+    let mut latest: Vec<(Locale, DateTime<Utc>)> = vec![];
+    for episode in seasons_episodes(seasons).await? {
+        let date = episode.premium_available_date;
+        if date > now {
+            continue;
+        }
+        match latest.iter_mut().find(|(l, _)| l == &episode.audio_locale) {
+            Some((_, latest_date)) => *latest_date = date.max(*latest_date),
+            None => latest.push((episode.audio_locale, date)),
+        }
+    }
+
+    Ok(LineupEntry {
+        series,
+        audio_locales,
+        subtitle_locales,
+        releases: latest
+            .into_iter()
+            .map(|(audio_locale, date)| LineupRelease {
+                audio_locale,
+                weekday: date.weekday(),
+                time: NaiveTime::from_hms_opt(date.hour(), date.minute(), 0).unwrap_or_default(),
+            })
+            .collect(),
+    })
+}
+
+/// Return all series which air in `simulcast_season`.
+async fn simulcast_series(
+    crunchyroll: &Crunchyroll,
+    simulcast_season: &SimulcastSeason,
+) -> Result<Vec<Series>> {
+    let mut browse = crunchyroll.browse(
+        BrowseOptions::default()
+            .simulcast_season(simulcast_season.id.clone())
+            .media_type(MediaType::Series),
+    );
+    browse.page_size(100);
+    let mut series = vec![];
+    while let Some(media) = browse.next().await {
+        if let MediaCollection::Series(s) = media? {
+            series.push(s)
+        }
+    }
+    Ok(series)
+}
+
+/// Return the seasons of `series` which air in `simulcast_season`.
+async fn simulcast_seasons_of(
+    series: &Series,
+    simulcast_season: &SimulcastSeason,
+) -> Result<Vec<Season>> {
+    Ok(series
+        .seasons()
+        .await?
+        .into_iter()
+        .filter(|season| season.season_tags.contains(&simulcast_season.id))
+        .collect())
+}
+
+/// Return all episodes of `seasons`. Up to [`MAX_CONCURRENT_REQUESTS`] seasons are requested
+/// concurrently.
+async fn seasons_episodes(seasons: Vec<Season>) -> Result<Vec<Episode>> {
+    Ok(stream::iter(seasons.iter().map(|s| s.episodes()))
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+        .try_collect::<Vec<Vec<Episode>>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Request the episodes of every series with `episodes_of`, with up to [`MAX_CONCURRENT_REQUESTS`]
+/// series at once. Returns the episodes of all series and the ids of the series whose episodes
+/// could not be requested.
+async fn series_episodes<'a, F, Fut>(
+    series: &'a [Series],
+    episodes_of: F,
+) -> (Vec<Episode>, Vec<String>)
+where
+    F: Fn(&'a Series) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Episode>>> + 'a,
+{
+    let results: Vec<(&Series, Result<Vec<Episode>>)> = stream::iter(series.iter().map(|s| {
+        let episodes = episodes_of(s);
+        async move { (s, episodes.await) }
+    }))
+    .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+    .collect()
+    .await;
+
+    let mut episodes = vec![];
+    let mut skipped_series = vec![];
+    for (series, result) in results {
+        match result {
+            Ok(series_episodes) => episodes.extend(series_episodes),
+            Err(_) => skipped_series.push(series.id.clone()),
+        }
+    }
+    (episodes, skipped_series)
+}
+
+impl Crunchyroll {
+    /// Return all episodes of `simulcast_season` which become available between `start`
+    /// (inclusive) and `end` (exclusive). Use [`Crunchyroll::simulcast_seasons`] to get all
+    /// simulcast seasons. A limited number of series of the simulcast season are requested
+    /// concurrently, series which fail to request are listed in
+    /// [`ReleaseCalendar::skipped_series`].
+    pub async fn simulcast_calendar(
+        &self,
+        simulcast_season: &SimulcastSeason,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ReleaseCalendar> {
+        let series = simulcast_series(self, simulcast_season).await?;
+
+        let (episodes, skipped_series) = series_episodes(&series, |s| async move {
+            seasons_episodes(simulcast_seasons_of(s, simulcast_season).await?).await
+        })
+        .await;
+
+        Ok(ReleaseCalendar::from_episodes(
+            start,
+            end,
+            episodes,
+            skipped_series,
+        ))
+    }
+
+    /// Return all episodes of the series on your watchlist which become available between `start`
+    /// (inclusive) and `end` (exclusive). Movie listings on the watchlist are ignored. A limited
+    /// number of series are requested concurrently, series which fail to request are listed in
+    /// [`ReleaseCalendar::skipped_series`].
+    pub async fn watchlist_calendar(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<ReleaseCalendar> {
+        let series: Vec<Series> = self
+            .watchlist(WatchlistOptions::default().media_type(MediaType::Series))
+            .try_collect::<Vec<WatchlistEntry>>()
+            .await?
+            .into_iter()
+            .filter_map(|entry| match entry.panel {
+                MediaCollection::Series(series) => Some(series),
+                _ => None,
+            })
+            .collect();
+
+        let (episodes, skipped_series) = series_episodes(&series, |s| async move {
+            seasons_episodes(s.seasons().await?).await
+        })
+        .await;
+
+        Ok(ReleaseCalendar::from_episodes(
+            start,
+            end,
+            episodes,
+            skipped_series,
+        ))
+    }
+}
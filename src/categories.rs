@@ -1,10 +1,17 @@
 use crate::common::{Image, V2BulkResult};
+use crate::error::CrunchyrollError;
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
 use crate::Result;
-use crate::{enum_values, Crunchyroll, Locale, Request};
+use crate::{enum_values, Crunchyroll, Executor, Locale, Request};
+use futures_util::{stream, StreamExt};
 use serde::Deserialize;
+use std::sync::Arc;
 
 enum_values! {
-    /// Video categories / genres
+    /// Video categories / genres. Categories which are not listed here (including sub categories)
+    /// are represented as [`Category::Custom`] with the category id as value, use
+    /// [`Crunchyroll::categories`] or [`Crunchyroll::category_tree`] to get all categories which
+    /// are currently available.
     pub enum Category {
         Action = "action"
         Adventure = "adventure"
@@ -30,6 +37,12 @@ impl From<CategoryInformation> for Category {
     }
 }
 
+impl From<SubCategoryInformation> for Category {
+    fn from(sub_category_information: SubCategoryInformation) -> Self {
+        Category::Custom(sub_category_information.id)
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -53,6 +66,9 @@ pub struct CategoryInformationLocalization {
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct CategoryInformation {
+    #[serde(skip)]
+    executor: Arc<Executor>,
+
     #[serde(rename = "id")]
     pub category: Category,
     pub slug: String,
@@ -63,6 +79,53 @@ pub struct CategoryInformation {
     pub localization: CategoryInformationLocalization,
 }
 
+impl CategoryInformation {
+    /// Returns all sub categories / sub genres of this category.
+    pub async fn sub_categories(&self) -> Result<Vec<SubCategoryInformation>> {
+        let endpoint = format!(
+            "https://www.crunchyroll.com/content/v2/discover/categories/{}/sub_categories",
+            self.category
+        );
+        Ok(self
+            .executor
+            .get(endpoint)
+            .apply_locale_query()
+            .request::<V2BulkResult<SubCategoryInformation>>()
+            .await?
+            .data)
+    }
+}
+
+/// A sub category / sub genre of a [`CategoryInformation`], e.g. `isekai` which is a sub category
+/// of [`Category::Fantasy`]. Can be used as [`Category`] via [`From`] (e.g. in
+/// [`crate::search::BrowseOptions::categories`]).
+#[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
+#[cfg_attr(not(feature = "__test_strict"), serde(default))]
+pub struct SubCategoryInformation {
+    pub id: String,
+    pub slug: String,
+
+    pub parent_category: Category,
+    pub tenant_category: String,
+
+    pub images: CategoryInformationImages,
+
+    /// A human readable title & description about the sub category.
+    pub localization: CategoryInformationLocalization,
+}
+
+/// A category with all its sub categories. See [`Crunchyroll::category_tree`].
+#[derive(Clone, Debug, Default)]
+pub struct CategoryTree {
+    pub category: CategoryInformation,
+    /// Empty if the sub categories could not be requested, see [`CategoryTree::error`].
+    pub sub_categories: Vec<SubCategoryInformation>,
+
+    /// Error which occurred while requesting the sub categories.
+    pub error: Option<CrunchyrollError>,
+}
+
 impl Crunchyroll {
     /// Returns all video categories.
     pub async fn categories(&self) -> Result<Vec<CategoryInformation>> {
@@ -75,4 +138,33 @@ impl Crunchyroll {
             .await?
             .data)
     }
+
+    /// Returns all video categories with their sub categories. The sub categories of a limited
+    /// number of categories are requested concurrently. If the sub categories of a category can't
+    /// be requested, the category is still returned, with [`CategoryTree::error`] set.
+    pub async fn category_tree(&self) -> Result<Vec<CategoryTree>> {
+        let categories = self.categories().await?;
+        let sub_categories: Vec<Result<Vec<SubCategoryInformation>>> =
+            stream::iter(categories.iter().map(|c| c.sub_categories()))
+                .buffered(MAX_CONCURRENT_REQUESTS)
+                .collect()
+                .await;
+
+        Ok(categories
+            .into_iter()
+            .zip(sub_categories)
+            .map(|(category, sub_categories)| match sub_categories {
+                Ok(sub_categories) => CategoryTree {
+                    category,
+                    sub_categories,
+                    error: None,
+                },
+                Err(e) => CategoryTree {
+                    category,
+                    sub_categories: vec![],
+                    error: Some(e),
+                },
+            })
+            .collect())
+    }
 }
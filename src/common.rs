@@ -1,9 +1,10 @@
-use crate::{Executor, Result};
+use crate::{Crunchyroll, Executor, Result};
 use futures_util::{Stream, StreamExt};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
+use std::io::Write;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -182,16 +183,49 @@ pub(crate) struct BulkResult<T: Default + DeserializeOwned + Request> {
 
 /// The standard representation of images how the api returns them.
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Image {
     pub source: String,
-    #[serde(rename(deserialize = "type"))]
+    #[serde(rename = "type")]
     pub image_type: String,
     pub height: u32,
     pub width: u32,
 }
 
+impl Image {
+    /// Width divided by height. `0` if the image has no height.
+    pub fn aspect_ratio(&self) -> f64 {
+        if self.height == 0 {
+            0.0
+        } else {
+            self.width as f64 / self.height as f64
+        }
+    }
+
+    /// Download the image with the http client of `crunchyroll`.
+    pub async fn download(&self, crunchyroll: &Crunchyroll) -> Result<Vec<u8>> {
+        Ok(crunchyroll
+            .executor
+            .client
+            .get(&self.source)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec())
+    }
+
+    /// Download the image and write it to `w`. Like all other `write_to` functions of this crate,
+    /// writing to `w` is blocking. The image is fully downloaded before anything is written, use
+    /// [`Image::download`] and write the bytes with an async writer if blocking is a problem.
+    pub async fn write_to(&self, crunchyroll: &Crunchyroll, w: &mut impl Write) -> Result<()> {
+        Ok(w.write_all(&self.download(crunchyroll).await?)?)
+    }
+}
+
 /// Helper trait for [`Crunchyroll::request`] generic returns.
 /// Must be implemented for every struct which is used as generic parameter for [`Crunchyroll::request`].
 #[doc(hidden)]
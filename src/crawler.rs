@@ -0,0 +1,298 @@
+//! Crawl the whole catalog.
+
+use crate::error::CrunchyrollError;
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
+use crate::search::BrowseOptions;
+use crate::{Crunchyroll, Episode, MediaCollection, MovieListing, Result, Season, Series};
+use futures_util::stream::FuturesUnordered;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Destination of everything a [`Crawler`] finds.
+#[async_trait::async_trait]
+pub trait CrawlSink: Send {
+    /// Write a single media. Called with every media in the order series / movie listing, then
+    /// its seasons, episodes or movies.
+    async fn write(&mut self, media: MediaCollection) -> Result<()>;
+
+    /// Called every time after a series / movie listing and all its children were written,
+    /// directly before the checkpoint is saved.
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// [`CrawlSink`] which writes every media as json in a separate line.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub struct JsonLinesSink<W: std::io::Write + Send> {
+    writer: W,
+}
+
+#[cfg(feature = "serde")]
+impl<W: std::io::Write + Send> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "serde")]
+#[async_trait::async_trait]
+impl<W: std::io::Write + Send> CrawlSink for JsonLinesSink<W> {
+    async fn write(&mut self, media: MediaCollection) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, &media)?;
+        Ok(self.writer.write_all(b"\n")?)
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+/// [`CrawlSink`] which calls a function with every media.
+pub struct CallbackSink<F: FnMut(MediaCollection) -> Result<()> + Send> {
+    callback: F,
+}
+
+impl<F: FnMut(MediaCollection) -> Result<()> + Send> CallbackSink<F> {
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: FnMut(MediaCollection) -> Result<()> + Send> CrawlSink for CallbackSink<F> {
+    async fn write(&mut self, media: MediaCollection) -> Result<()> {
+        (self.callback)(media)
+    }
+}
+
+/// Progress of a crawl which is saved to disk so that an interrupted crawl can be resumed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CrawlCheckpoint {
+    /// Ids of all series / movie listings which were completely crawled and written to the sink.
+    pub completed: HashSet<String>,
+}
+
+impl CrawlCheckpoint {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    /// Write to a temporary file first and rename it afterwards, so the checkpoint doesn't get
+    /// corrupted if the process is killed while writing.
+    fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        Ok(std::fs::rename(&tmp_path, path)?)
+    }
+}
+
+/// Number of media a [`Crawler`] has written to its sink.
+#[derive(Clone, Debug, Default)]
+pub struct CrawlStats {
+    pub series: u32,
+    pub seasons: u32,
+    pub episodes: u32,
+    pub movie_listings: u32,
+    pub movies: u32,
+    /// Series / movie listings which were skipped because they were already crawled according to
+    /// the checkpoint.
+    pub skipped: u32,
+    /// Ids of series / movie listings which could not be crawled (e.g. because they aren't
+    /// available in your region) and the error which occurred. Nothing of them was written to the
+    /// sink and they aren't marked as completed in the checkpoint, so they are crawled again when
+    /// resuming.
+    pub failed: Vec<(String, CrunchyrollError)>,
+}
+
+/// Crawls all series and movie listings of the catalog with all their seasons, episodes and
+/// movies. Get it via [`Crunchyroll::crawler`].
+pub struct Crawler {
+    crunchyroll: Crunchyroll,
+
+    browse_options: BrowseOptions,
+    concurrency: usize,
+    checkpoint: Option<PathBuf>,
+    originals_only: bool,
+}
+
+impl Crawler {
+    /// Options which are used to get the series / movie listings which should be crawled. Default
+    /// is the whole catalog.
+    pub fn browse_options(mut self, browse_options: BrowseOptions) -> Self {
+        self.browse_options = browse_options;
+        self
+    }
+
+    /// How many series / movie listings are crawled at the same time. Default is 4.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// File where the progress is stored. If the file already exists, all series / movie listings
+    /// which are marked as completed in it are skipped.
+    pub fn checkpoint(mut self, path: impl AsRef<Path>) -> Self {
+        self.checkpoint = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Only crawl the original versions of seasons and skip their dubs. Default is `false`.
+    pub fn originals_only(mut self, originals_only: bool) -> Self {
+        self.originals_only = originals_only;
+        self
+    }
+
+    /// Crawl the catalog and write everything to `sink`. Every media is only written once, even
+    /// if it appears multiple times while crawling. The catalog is browsed page by page while
+    /// crawling, so crawling starts immediately and an interrupted crawl of a big catalog can be
+    /// resumed with the checkpoint. Series / movie listings which fail to crawl don't abort the
+    /// crawl, they are listed in [`CrawlStats::failed`]. An error is only returned if browsing the
+    /// catalog or writing to the sink / checkpoint fails.
+    pub async fn run(&self, sink: &mut impl CrawlSink) -> Result<CrawlStats> {
+        let mut checkpoint = match &self.checkpoint {
+            Some(path) => CrawlCheckpoint::load(path)?,
+            None => CrawlCheckpoint::default(),
+        };
+        let mut stats = CrawlStats::default();
+
+        let mut browse = self.crunchyroll.browse(self.browse_options.clone());
+        browse.page_size(100);
+        let mut browse_finished = false;
+        let mut crawling = FuturesUnordered::new();
+        let mut queued = HashSet::new();
+        let mut seen = HashSet::new();
+        loop {
+            // keep `concurrency` series / movie listings crawling while browsing further
+            while !browse_finished && crawling.len() < self.concurrency {
+                let media = match browse.next().await {
+                    Some(media) => media?,
+                    None => {
+                        browse_finished = true;
+                        break;
+                    }
+                };
+                let id = media.id().to_string();
+                if checkpoint.completed.contains(&id) {
+                    stats.skipped += 1;
+                    continue;
+                }
+                if queued.insert(id.clone()) {
+                    crawling.push(async move { (id, self.crawl(media).await) })
+                }
+            }
+
+            let (id, media) = match crawling.next().await {
+                Some((id, Ok(media))) => (id, media),
+                Some((id, Err(e))) => {
+                    stats.failed.push((id, e));
+                    continue;
+                }
+                None => break,
+            };
+
+            for m in media {
+                if !seen.insert(m.id().to_string()) {
+                    continue;
+                }
+                match &m {
+                    MediaCollection::Series(_) => stats.series += 1,
+                    MediaCollection::Season(_) => stats.seasons += 1,
+                    MediaCollection::Episode(_) => stats.episodes += 1,
+                    MediaCollection::MovieListing(_) => stats.movie_listings += 1,
+                    MediaCollection::Movie(_) => stats.movies += 1,
+                    MediaCollection::MusicVideo(_)
+                    | MediaCollection::Concert(_)
+                    | MediaCollection::Artist(_) => (),
+                }
+                sink.write(m).await?
+            }
+            sink.flush().await?;
+
+            checkpoint.completed.insert(id);
+            if let Some(path) = &self.checkpoint {
+                checkpoint.save(path)?
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Return `media` and all its children.
+    pub(crate) async fn crawl(&self, media: MediaCollection) -> Result<Vec<MediaCollection>> {
+        match media {
+            MediaCollection::Series(series) => self.crawl_series(series).await,
+            MediaCollection::MovieListing(movie_listing) => {
+                self.crawl_movie_listing(movie_listing).await
+            }
+            _ => Ok(vec![media]),
+        }
+    }
+
+    async fn crawl_series(&self, series: Series) -> Result<Vec<MediaCollection>> {
+        let seasons: Vec<Season> = series
+            .seasons()
+            .await?
+            .into_iter()
+            .filter(|season| !self.originals_only || is_original(season))
+            .collect();
+        let episodes: Vec<Vec<Episode>> = stream::iter(seasons.iter().map(|s| s.episodes()))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        let mut result = vec![series.into()];
+        for (season, episodes) in seasons.into_iter().zip(episodes) {
+            result.push(season.into());
+            result.extend(episodes.into_iter().map(MediaCollection::from))
+        }
+        Ok(result)
+    }
+
+    async fn crawl_movie_listing(
+        &self,
+        movie_listing: MovieListing,
+    ) -> Result<Vec<MediaCollection>> {
+        let movies = movie_listing.movies().await?;
+
+        let mut result = vec![movie_listing.into()];
+        result.extend(movies.into_iter().map(MediaCollection::from));
+        Ok(result)
+    }
+}
+
+/// If `season` isn't a dub of another season. Seasons without version information are treated as
+/// original.
+fn is_original(season: &Season) -> bool {
+    season
+        .versions
+        .as_ref()
+        .and_then(|versions| versions.iter().find(|v| v.id == season.id))
+        .map(|v| v.original)
+        .unwrap_or(true)
+}
+
+impl Crunchyroll {
+    /// Create a [`Crawler`] which crawls the whole catalog.
+    pub fn crawler(&self) -> Crawler {
+        Crawler {
+            crunchyroll: self.clone(),
+            browse_options: BrowseOptions::default(),
+            concurrency: 4,
+            checkpoint: None,
+            originals_only: false,
+        }
+    }
+}
@@ -3,26 +3,28 @@ use reqwest::Client;
 use std::sync::Arc;
 
 enum_values! {
-    /// Enum of supported languages by Crunchyroll.
+    /// Enum of supported languages by Crunchyroll. The values are
+    /// [BCP 47](https://www.rfc-editor.org/info/bcp47) language tags, use [`Locale::from_bcp47`]
+    /// to parse tags which might not be normalized.
     #[allow(non_camel_case_types)]
     #[derive(Hash, Ord, PartialOrd)]
     pub enum Locale {
-        ar_ME = "ar"
-        ar_SA = "ar"
-        de_DE = "de"
-        en_IN = "en"
-        en_US = "en"
-        es_419 = "es"
-        es_ES = "es"
-        es_LA = "es"
-        fr_FR = "fr"
-        hi_IN = "hi"
-        it_IT = "it"
-        ja_JP = "ja"
-        pt_BR = "pt"
-        pt_PT = "pt"
-        ru_RU = "ru"
-        zh_CN = "zh"
+        ar_ME = "ar-ME"
+        ar_SA = "ar-SA"
+        de_DE = "de-DE"
+        en_IN = "en-IN"
+        en_US = "en-US"
+        es_419 = "es-419"
+        es_ES = "es-ES"
+        es_LA = "es-LA"
+        fr_FR = "fr-FR"
+        hi_IN = "hi-IN"
+        it_IT = "it-IT"
+        ja_JP = "ja-JP"
+        pt_BR = "pt-BR"
+        pt_PT = "pt-PT"
+        ru_RU = "ru-RU"
+        zh_CN = "zh-CN"
     }
 }
 
@@ -48,26 +50,10 @@ impl Locale {
         ]
     }
 
+    /// English name of the locale. Use [`Locale::display_name`] to get the name in other
+    /// languages.
     pub fn to_human_readable(&self) -> String {
-        match self {
-            Locale::ar_ME => "[ToonsHub] Arabic",
-            Locale::ar_SA => "[ToonsHub] Arabic (Saudi Arabia)",
-            Locale::de_DE => "[ToonsHub] German",
-            Locale::en_IN => "[ToonsHub] English (India)",
-            Locale::en_US => "[ToonsHub] English (US)",
-            Locale::es_419 | Locale::es_LA => "[ToonsHub] Spanish (Latin America)",
-            Locale::es_ES => "[ToonsHub] Spanish (European)",
-            Locale::fr_FR => "[ToonsHub] French",
-            Locale::hi_IN => "[ToonsHub] Hindi",
-            Locale::it_IT => "[ToonsHub] Italian",
-            Locale::ja_JP => "[ToonsHub] Japanese",
-            Locale::pt_BR => "[ToonsHub] Portuguese (Brazil)",
-            Locale::pt_PT => "[ToonsHub] Portuguese (Europe)",
-            Locale::ru_RU => "[ToonsHub] Russian",
-            Locale::zh_CN => "[ToonsHub] Chinese (China)",
-            Locale::Custom(custom) => custom.as_str(),
-        }
-        .to_string()
+        self.display_name(&Locale::en_US)
     }
 }
 
@@ -104,6 +90,16 @@ impl Crunchyroll {
     pub async fn session_token(&self) -> SessionToken {
         self.executor.config.lock().await.session_token.clone()
     }
+
+    /// Attach this session to a value which was deserialized with the `serde` feature. A
+    /// deserialized value has no session, so every function of it which requests the api would
+    /// fail until it's attached.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub async fn attach<T: crate::Request>(&self, mut value: T) -> T {
+        value.__set_executor(self.executor.clone()).await;
+        value
+    }
 }
 
 mod auth {
@@ -192,6 +188,10 @@ mod auth {
 
         #[allow(unused)]
         pub(crate) fixes: ExecutorFixes,
+
+        /// If the executor belongs to a [`crate::media::Detached`] media. Every request made with it
+        /// fails.
+        pub(crate) detached: bool,
     }
 
     impl Executor {
@@ -219,6 +219,8 @@ mod auth {
             self: &Arc<Self>,
             mut req: RequestBuilder,
         ) -> Result<T> {
+            self.check_attached()?;
+
             let mut config = self.config.lock().await;
             if config.session_expire <= Utc::now() {
                 let login_response = match config.session_token.clone() {
@@ -355,6 +357,17 @@ mod auth {
         }
     }
 
+    impl Executor {
+        pub(crate) fn check_attached(&self) -> Result<()> {
+            if self.detached {
+                return Err(CrunchyrollError::Input(
+                    "detached media, call Crunchyroll::attach or Detached::attach first".into(),
+                ));
+            }
+            Ok(())
+        }
+    }
+
     impl Default for Executor {
         fn default() -> Self {
             Self {
@@ -379,6 +392,7 @@ mod auth {
                     locale_name_parsing: false,
                     season_number: false,
                 },
+                detached: false,
             }
         }
     }
@@ -423,7 +437,13 @@ mod auth {
         }
 
         pub(crate) async fn request_raw(self) -> Result<Vec<u8>> {
-            Ok(self.builder.send().await?.bytes().await?.to_vec())
+            Ok(self.send().await?.bytes().await?.to_vec())
+        }
+
+        /// Send the request without checking the response, the status must be checked manually.
+        pub(crate) async fn send(self) -> Result<reqwest::Response> {
+            self.executor.check_attached()?;
+            Ok(self.builder.send().await?)
         }
     }
 
@@ -701,6 +721,7 @@ mod auth {
                         }),
                     },
                     fixes: self.fixes,
+                    detached: false,
                 }),
             };
 
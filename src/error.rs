@@ -26,6 +26,9 @@ pub enum CrunchyrollError {
 
     /// Generally malformed or invalid user input.
     Input(CrunchyrollErrorContext<()>),
+
+    /// Reading from or writing to a file or another io resource (like a database) failed.
+    Io(CrunchyrollErrorContext<()>),
 }
 
 impl Display for CrunchyrollError {
@@ -36,6 +39,7 @@ impl Display for CrunchyrollError {
             CrunchyrollError::Decode(context) => write!(f, "{context}"),
             CrunchyrollError::Authentication(context) => write!(f, "{context}"),
             CrunchyrollError::Input(context) => write!(f, "{context}"),
+            CrunchyrollError::Io(context) => write!(f, "{context}"),
         }
     }
 }
@@ -48,6 +52,19 @@ impl From<serde_json::Error> for CrunchyrollError {
     }
 }
 
+impl From<std::io::Error> for CrunchyrollError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(CrunchyrollErrorContext::new(err.to_string()))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for CrunchyrollError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Io(CrunchyrollErrorContext::new(err.to_string()))
+    }
+}
+
 impl From<reqwest::Error> for CrunchyrollError {
     fn from(err: reqwest::Error) -> Self {
         let mut context: CrunchyrollErrorContext<()> =
@@ -1,11 +1,16 @@
 use crate::common::{Pagination, V2BulkResult, V2TypeBulkResult};
-use crate::media::MediaType;
+use crate::error::CrunchyrollError;
+use crate::list::WatchlistOptions;
+use crate::media::{Media, MediaType};
 use crate::search::{BrowseOptions, BrowseSortType};
-use crate::{Crunchyroll, MediaCollection, Request, Series};
+use crate::{Concert, Crunchyroll, Executor, MediaCollection, MusicVideo, Request, Result, Series};
 use chrono::{DateTime, Utc};
-use futures_util::FutureExt;
-use serde::de::Error;
+use futures_util::future::{try_join3, try_join_all};
+use futures_util::{FutureExt, StreamExt};
+use serde::de::{DeserializeOwned, Error};
 use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Clone, Debug, Default, Deserialize, Request)]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
@@ -125,7 +130,7 @@ impl Default for HomeFeed {
 }
 
 impl<'de> Deserialize<'de> for HomeFeed {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -229,7 +234,7 @@ impl<'de> Deserialize<'de> for HomeFeed {
                             .as_array()
                             .ok_or_else(|| type_error("ids", "string list"))?
                             .iter()
-                            .map(|v| v.to_string())
+                            .filter_map(|v| v.as_str().map(|id| id.to_string()))
                             .collect();
                         Ok(Self::ConcertFeed(ids))
                     }
@@ -238,7 +243,7 @@ impl<'de> Deserialize<'de> for HomeFeed {
                             .as_array()
                             .ok_or_else(|| type_error("ids", "string list"))?
                             .iter()
-                            .map(|v| v.to_string())
+                            .filter_map(|v| v.as_str().map(|id| id.to_string()))
                             .collect();
                         Ok(Self::MusicVideoFeed(ids))
                     }
@@ -262,6 +267,65 @@ impl<'de> Deserialize<'de> for HomeFeed {
     }
 }
 
+/// A [`SeriesFeed`] with resolved series.
+#[derive(Clone, Debug, Default)]
+pub struct ResolvedSeriesFeed {
+    pub title: String,
+
+    pub description: String,
+
+    pub series: Vec<Series>,
+}
+
+/// A [`SimilarFeed`] with the series it's based on and the similar media.
+#[derive(Clone, Debug, Default)]
+pub struct ResolvedSimilarFeed {
+    pub title: String,
+
+    pub description: String,
+
+    /// The series the [`ResolvedSimilarFeed::similar`] media is similar to.
+    pub similar_to: Series,
+    pub similar: Vec<MediaCollection>,
+}
+
+/// [`HomeFeed`] with all ids and marker panels resolved into their media. Panels which only
+/// reference a list (e.g. [`HomeFeed::Watchlist`]) contain the first
+/// [`RESOLVED_PANEL_SIZE`] items of it. Get it via [`Crunchyroll::home_feed_resolved`].
+#[derive(Clone, Debug)]
+pub enum ResolvedHomeFeed {
+    /// See [`HomeFeed::CarouselFeed`].
+    CarouselFeed(Vec<FeedCarousel>),
+    /// See [`HomeFeed::Series`].
+    Series(Series),
+    /// Recommendations for you.
+    Recommendation(Vec<MediaCollection>),
+    /// Your recently watched episodes and movies.
+    History(Vec<MediaCollection>),
+    /// See [`HomeFeed::Banner`].
+    Banner(FeedBanner),
+    /// Series and movie listings on your watchlist.
+    Watchlist(Vec<MediaCollection>),
+    /// See [`HomeFeed::SeriesFeed`]. Series which could not be found are omitted.
+    SeriesFeed(ResolvedSeriesFeed),
+    /// See [`HomeFeed::MusicVideoFeed`]. Music videos which could not be found are omitted.
+    MusicVideoFeed(Vec<MusicVideo>),
+    /// See [`HomeFeed::ConcertFeed`]. Concerts which could not be found are omitted.
+    ConcertFeed(Vec<Concert>),
+    /// Top news.
+    NewsFeed(Vec<NewsFeed>),
+    /// Result of [`HomeFeed::Browse`].
+    Browse(Vec<MediaCollection>),
+    /// See [`HomeFeed::SimilarTo`].
+    SimilarTo(ResolvedSimilarFeed),
+    /// See [`HomeFeed::Unknown`].
+    Unknown(serde_json::Map<String, serde_json::Value>),
+}
+
+/// Number of items [`Crunchyroll::home_feed_resolved`] requests for panels which only reference a
+/// list.
+pub const RESOLVED_PANEL_SIZE: u32 = 20;
+
 pub struct NewsFeedResult {
     pub top_news: Pagination<NewsFeed>,
     pub latest_news: Pagination<NewsFeed>,
@@ -272,6 +336,9 @@ pub struct NewsFeedResult {
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct NewsFeed {
+    #[serde(skip)]
+    executor: Arc<Executor>,
+
     pub title: String,
     pub description: String,
 
@@ -285,6 +352,245 @@ pub struct NewsFeed {
     pub news_link: String,
 }
 
+impl NewsFeed {
+    /// The slug of the news, which is the path of [`NewsFeed::news_link`] without the locale prefix
+    /// localized news have, e.g. `news/latest/2023/3/1/dubs`. [`None`] if the link is no
+    /// Crunchyroll link.
+    pub fn slug(&self) -> Option<&str> {
+        let (_, path) = self.news_link.split_once("crunchyroll.com/")?;
+        let path = path
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(path)
+            .trim_end_matches('/');
+        // links of localized news are prefixed with their locale, e.g. `de/news/...`
+        match path.split_once('/') {
+            Some((prefix, slug)) if prefix != "news" && slug.starts_with("news/") => Some(slug),
+            _ => Some(path),
+        }
+    }
+
+    /// Request the full article of this news.
+    pub async fn article(&self) -> Result<NewsArticle> {
+        let slug = match self.slug() {
+            Some(slug) => slug,
+            None => {
+                return Err(CrunchyrollError::Input(
+                    format!("'{}' is no crunchyroll news link", self.news_link).into(),
+                ))
+            }
+        };
+        let endpoint = format!(
+            "https://cr-news-api-service.prd.crunchyrollsvc.com/v1/{}/stories",
+            self.executor.details.locale
+        );
+        Ok(self
+            .executor
+            .get(endpoint)
+            .query(&[("slug", slug)])
+            .request::<NewsStoryResult>()
+            .await?
+            .story)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Request)]
+#[serde(default)]
+struct NewsStoryResult {
+    story: NewsArticle,
+}
+
+/// Full article of a [`NewsFeed`]. Get it via [`NewsFeed::article`].
+#[derive(Clone, Debug, Default, Deserialize, Request)]
+#[serde(default)]
+pub struct NewsArticle {
+    #[serde(rename = "name")]
+    pub title: String,
+    #[serde(rename = "full_slug")]
+    pub slug: String,
+
+    #[serde(rename = "first_published_at")]
+    pub publish_date: Option<DateTime<Utc>>,
+    #[serde(rename = "published_at")]
+    pub update_date: Option<DateTime<Utc>>,
+
+    #[serde(rename = "tag_list")]
+    pub tags: Vec<String>,
+
+    /// Raw content of the article. Use [`NewsArticle::text`] to get the article text.
+    pub content: serde_json::Map<String, serde_json::Value>,
+}
+
+impl NewsArticle {
+    /// The headline of the article.
+    pub fn headline(&self) -> Option<&str> {
+        self.content.get("headline")?.as_str()
+    }
+
+    /// The article body as plain text. Paragraphs are separated by an empty line.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        if let Some(body) = self.content.get("body") {
+            rich_text(body, &mut text)
+        }
+        text.trim().to_string()
+    }
+}
+
+/// Append all text of a rich text `node` to `text`.
+fn rich_text(node: &serde_json::Value, text: &mut String) {
+    match node {
+        serde_json::Value::Array(nodes) => nodes.iter().for_each(|n| rich_text(n, text)),
+        serde_json::Value::Object(node) => {
+            match node.get("type").and_then(|t| t.as_str()) {
+                Some("text") => {
+                    if let Some(t) = node.get("text").and_then(|t| t.as_str()) {
+                        text.push_str(t)
+                    }
+                }
+                Some("hard_break") => text.push('\n'),
+                _ => (),
+            }
+            if let Some(content) = node.get("content") {
+                rich_text(content, text)
+            }
+            if matches!(
+                node.get("type").and_then(|t| t.as_str()),
+                Some("paragraph" | "heading" | "blockquote" | "list_item")
+            ) {
+                text.push_str("\n\n")
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Document format in which news can be exported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NewsExportFormat {
+    /// [RSS 2.0](https://www.rssboard.org/rss-specification).
+    Rss,
+    /// [Atom](https://www.rfc-editor.org/rfc/rfc4287).
+    Atom,
+}
+
+impl NewsExportFormat {
+    /// Create a document of `news` in this format.
+    pub fn export(&self, news: &[NewsFeed]) -> String {
+        let title = "Crunchyroll News";
+        let link = "https://www.crunchyroll.com/news";
+
+        let mut lines = vec![r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string()];
+        match self {
+            NewsExportFormat::Rss => {
+                lines.extend([
+                    r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">"#
+                        .to_string(),
+                    "<channel>".to_string(),
+                    format!("<title>{}</title>", title),
+                    format!("<link>{}</link>", link),
+                    format!("<description>{}</description>", title),
+                ]);
+                for item in news {
+                    lines.extend([
+                        "<item>".to_string(),
+                        format!("<title>{}</title>", escape_xml(&item.title)),
+                        format!("<link>{}</link>", escape_xml(&item.news_link)),
+                        format!("<guid>{}</guid>", escape_xml(&item.news_link)),
+                        format!(
+                            "<description>{}</description>",
+                            escape_xml(&item.description)
+                        ),
+                        // `<author>` must be an email address, so the dublin core creator is used
+                        format!("<dc:creator>{}</dc:creator>", escape_xml(&item.creator)),
+                        format!("<pubDate>{}</pubDate>", item.publish_date.to_rfc2822()),
+                    ]);
+                    if !item.image_link.is_empty() {
+                        lines.push(format!(
+                            r#"<enclosure url="{}" length="0" type="{}"/>"#,
+                            escape_xml(&item.image_link),
+                            image_mime_type(&item.image_link)
+                        ))
+                    }
+                    lines.push("</item>".to_string())
+                }
+                lines.extend(["</channel>".to_string(), "</rss>".to_string()])
+            }
+            NewsExportFormat::Atom => {
+                let updated = news
+                    .iter()
+                    .map(|n| n.publish_date)
+                    .max()
+                    .unwrap_or_else(Utc::now);
+                lines.extend([
+                    r#"<feed xmlns="http://www.w3.org/2005/Atom">"#.to_string(),
+                    format!("<title>{}</title>", title),
+                    format!(r#"<link href="{}"/>"#, link),
+                    format!("<id>{}</id>", link),
+                    format!("<updated>{}</updated>", updated.to_rfc3339()),
+                ]);
+                for item in news {
+                    lines.extend([
+                        "<entry>".to_string(),
+                        format!("<title>{}</title>", escape_xml(&item.title)),
+                        format!(r#"<link href="{}"/>"#, escape_xml(&item.news_link)),
+                        format!("<id>{}</id>", escape_xml(&item.news_link)),
+                        format!("<updated>{}</updated>", item.publish_date.to_rfc3339()),
+                        format!("<summary>{}</summary>", escape_xml(&item.description)),
+                        format!(
+                            "<author><name>{}</name></author>",
+                            escape_xml(&item.creator)
+                        ),
+                        "</entry>".to_string(),
+                    ])
+                }
+                lines.push("</feed>".to_string())
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Guess the mime type of an image by the file extension of its url.
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let extension = path
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "avif" => "image/avif",
+        _ => "application/octet-stream",
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl Pagination<NewsFeed> {
+    /// Request up to `limit` news and create a document of them in `format`.
+    pub async fn export(mut self, format: NewsExportFormat, limit: u32) -> Result<String> {
+        self.page_size(limit.min(100));
+        let news = self
+            .take(limit as usize)
+            .collect::<Vec<Result<NewsFeed>>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<NewsFeed>>>()?;
+        Ok(format.export(&news))
+    }
+}
+
 impl Crunchyroll {
     /// Returns the home feed (shown when visiting the Crunchyroll index page).
     pub fn home_feed(&self) -> Pagination<HomeFeed> {
@@ -313,6 +619,114 @@ impl Crunchyroll {
         )
     }
 
+    /// Returns the home feed like [`Crunchyroll::home_feed`] but with every panel resolved into its
+    /// media. All series, music video and concert ids of all panels are requested in batches,
+    /// panels which only reference a list are requested concurrently. Ids which can't be found
+    /// are omitted from their panel, [`HomeFeed::SimilarTo`] panels whose series can't be found
+    /// are omitted completely.
+    pub async fn home_feed_resolved(&self) -> Result<Vec<ResolvedHomeFeed>> {
+        let mut home_feed = self.home_feed();
+        home_feed.page_size(100);
+        let mut feed = vec![];
+        while let Some(panel) = home_feed.next().await {
+            feed.push(panel?)
+        }
+
+        let mut series_ids = vec![];
+        let mut music_video_ids = vec![];
+        let mut concert_ids = vec![];
+        for panel in &feed {
+            match panel {
+                HomeFeed::SeriesFeed(series_feed) => series_ids.extend(series_feed.ids.clone()),
+                HomeFeed::SimilarTo(similar_feed) => {
+                    series_ids.push(similar_feed.similar_id.clone())
+                }
+                HomeFeed::MusicVideoFeed(ids) => music_video_ids.extend(ids.clone()),
+                HomeFeed::ConcertFeed(ids) => concert_ids.extend(ids.clone()),
+                _ => (),
+            }
+        }
+        let (series, music_videos, concerts) = try_join3(
+            media_by_ids::<Series>(self, series_ids),
+            media_by_ids::<MusicVideo>(self, music_video_ids),
+            media_by_ids::<Concert>(self, concert_ids),
+        )
+        .await?;
+
+        let resolved = try_join_all(
+            feed.into_iter()
+                .map(|panel| self.resolve_panel(panel, &series, &music_videos, &concerts)),
+        )
+        .await?;
+
+        Ok(resolved.into_iter().flatten().collect())
+    }
+
+    /// Resolve a single panel of the home feed. [`None`] if the panel should be omitted.
+    async fn resolve_panel(
+        &self,
+        panel: HomeFeed,
+        series: &HashMap<String, Series>,
+        music_videos: &HashMap<String, MusicVideo>,
+        concerts: &HashMap<String, Concert>,
+    ) -> Result<Option<ResolvedHomeFeed>> {
+        Ok(Some(match panel {
+            HomeFeed::CarouselFeed(carousel) => ResolvedHomeFeed::CarouselFeed(carousel),
+            HomeFeed::Series(series) => ResolvedHomeFeed::Series(series),
+            HomeFeed::Recommendation => {
+                ResolvedHomeFeed::Recommendation(first_page(self.recommendations()).await?)
+            }
+            HomeFeed::History => ResolvedHomeFeed::History(
+                first_page(self.watch_history())
+                    .await?
+                    .into_iter()
+                    .map(|entry| entry.panel)
+                    .collect(),
+            ),
+            HomeFeed::Banner(banner) => ResolvedHomeFeed::Banner(banner),
+            HomeFeed::Watchlist => ResolvedHomeFeed::Watchlist(
+                first_page(self.watchlist(WatchlistOptions::default()))
+                    .await?
+                    .into_iter()
+                    .map(|entry| entry.panel)
+                    .collect(),
+            ),
+            HomeFeed::SeriesFeed(series_feed) => ResolvedHomeFeed::SeriesFeed(ResolvedSeriesFeed {
+                title: series_feed.title,
+                description: series_feed.description,
+                series: resolve_ids(series, &series_feed.ids),
+            }),
+            HomeFeed::MusicVideoFeed(ids) => {
+                ResolvedHomeFeed::MusicVideoFeed(resolve_ids(music_videos, &ids))
+            }
+            HomeFeed::ConcertFeed(ids) => {
+                ResolvedHomeFeed::ConcertFeed(resolve_ids(concerts, &ids))
+            }
+            HomeFeed::NewsFeed => {
+                ResolvedHomeFeed::NewsFeed(first_page(self.news_feed().top_news).await?)
+            }
+            HomeFeed::Browse(browse_options) => {
+                ResolvedHomeFeed::Browse(first_page(self.browse(browse_options)).await?)
+            }
+            HomeFeed::SimilarTo(similar_feed) => {
+                // other panels omit ids which can't be found, this panel has only one id so it's
+                // omitted completely
+                let similar_to = match series.get(&similar_feed.similar_id) {
+                    Some(series) => series.clone(),
+                    None => return Ok(None),
+                };
+                let similar = first_page(similar_to.similar()).await?;
+                ResolvedHomeFeed::SimilarTo(ResolvedSimilarFeed {
+                    title: similar_feed.title,
+                    description: similar_feed.description,
+                    similar_to,
+                    similar,
+                })
+            }
+            HomeFeed::Unknown(map) => ResolvedHomeFeed::Unknown(map),
+        }))
+    }
+
     /// Returns Crunchyroll news.
     pub fn news_feed(&self) -> NewsFeedResult {
         NewsFeedResult {
@@ -361,12 +775,12 @@ impl Crunchyroll {
                             .apply_preferred_audio_locale_query()
                             .request()
                             .await?;
-                        let top_news = result
+                        let latest_news = result
                             .data
                             .into_iter()
-                            .find(|p| p.result_type == "top_news")
+                            .find(|p| p.result_type == "latest_news")
                             .unwrap_or_default();
-                        Ok((top_news.items, top_news.total))
+                        Ok((latest_news.items, latest_news.total))
                     }
                     .boxed()
                 },
@@ -404,3 +818,43 @@ impl Crunchyroll {
         )
     }
 }
+
+/// Request all `ids` in batches and return every found media by its id. Ids which can't be found
+/// are omitted, every other error (e.g. a failed request) is returned.
+async fn media_by_ids<M>(crunchyroll: &Crunchyroll, ids: Vec<String>) -> Result<HashMap<String, M>>
+where
+    M: Media + TryFrom<MediaCollection, Error = CrunchyrollError>,
+{
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut found = HashMap::new();
+    for (id, media) in ids.iter().zip(crunchyroll.media_from_ids::<M>(&ids).await) {
+        match media {
+            Ok(media) => {
+                found.insert(id.clone(), media);
+            }
+            Err(CrunchyrollError::Input(_)) => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(found)
+}
+
+/// Return the media of `ids` in their order, ids which aren't in `media` are skipped.
+fn resolve_ids<M: Clone>(media: &HashMap<String, M>, ids: &[String]) -> Vec<M> {
+    ids.iter().filter_map(|id| media.get(id).cloned()).collect()
+}
+
+/// Return the first [`RESOLVED_PANEL_SIZE`] items of `pagination`.
+async fn first_page<T: Default + DeserializeOwned + Request>(
+    mut pagination: Pagination<T>,
+) -> Result<Vec<T>> {
+    pagination.page_size(RESOLVED_PANEL_SIZE);
+    pagination
+        .take(RESOLVED_PANEL_SIZE as usize)
+        .collect::<Vec<Result<T>>>()
+        .await
+        .into_iter()
+        .collect()
+}
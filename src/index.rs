@@ -0,0 +1,443 @@
+//! Local full-text index of catalog metadata which can be searched offline.
+
+use crate::{Episode, Locale, MediaCollection, MovieListing, MusicVideo, Result, Series};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+const TITLE_WEIGHT: f32 = 3.0;
+const TAG_WEIGHT: f32 = 2.0;
+const DESCRIPTION_WEIGHT: f32 = 1.0;
+
+const EXACT_MATCH_FACTOR: f32 = 1.0;
+const PREFIX_MATCH_FACTOR: f32 = 0.8;
+const TYPO_MATCH_FACTOR: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum IndexMediaType {
+    Series,
+    MovieListing,
+    Episode,
+    MusicVideo,
+}
+
+/// Searchable metadata of a media. Create it from a [`Series`], [`MovieListing`], [`Episode`] or
+/// [`MusicVideo`] via [`From`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IndexDocument {
+    pub id: String,
+    pub media_type: IndexMediaType,
+
+    /// All titles of the media. Contains more than one title if the media was indexed in multiple
+    /// locales.
+    pub titles: Vec<String>,
+    pub descriptions: Vec<String>,
+    /// Keywords, season tags, categories and genres.
+    pub tags: Vec<String>,
+
+    pub audio_locales: Vec<Locale>,
+    pub subtitle_locales: Vec<Locale>,
+
+    pub is_mature: bool,
+    pub is_premium_only: bool,
+}
+
+impl IndexDocument {
+    /// Create a document from `media`. [`None`] if the media can't be indexed (e.g. concerts).
+    pub fn from_media_collection(media: &MediaCollection) -> Option<Self> {
+        match media {
+            MediaCollection::Series(series) => Some(series.into()),
+            MediaCollection::MovieListing(movie_listing) => Some(movie_listing.into()),
+            MediaCollection::Episode(episode) => Some(episode.into()),
+            MediaCollection::MusicVideo(music_video) => Some(music_video.into()),
+            MediaCollection::Season(_)
+            | MediaCollection::Movie(_)
+            | MediaCollection::Concert(_)
+            | MediaCollection::Artist(_) => None,
+        }
+    }
+
+    /// Merge the text and locales of `other` into this document.
+    fn merge(&mut self, other: IndexDocument) {
+        fn extend_unique<T: PartialEq>(target: &mut Vec<T>, source: Vec<T>) {
+            for item in source {
+                if !target.contains(&item) {
+                    target.push(item)
+                }
+            }
+        }
+
+        extend_unique(&mut self.titles, other.titles);
+        extend_unique(&mut self.descriptions, other.descriptions);
+        extend_unique(&mut self.tags, other.tags);
+        extend_unique(&mut self.audio_locales, other.audio_locales);
+        extend_unique(&mut self.subtitle_locales, other.subtitle_locales);
+        self.is_mature = other.is_mature;
+        self.is_premium_only = other.is_premium_only;
+    }
+
+    /// Weight of every token of this document.
+    fn token_weights(&self) -> HashMap<String, f32> {
+        let mut weights = HashMap::new();
+        for (texts, weight) in [
+            (&self.titles, TITLE_WEIGHT),
+            (&self.tags, TAG_WEIGHT),
+            (&self.descriptions, DESCRIPTION_WEIGHT),
+        ] {
+            for token in texts.iter().flat_map(|t| tokenize(t)) {
+                *weights.entry(token).or_default() += weight
+            }
+        }
+        weights
+    }
+}
+
+impl From<&Series> for IndexDocument {
+    fn from(series: &Series) -> Self {
+        Self {
+            id: series.id.clone(),
+            media_type: IndexMediaType::Series,
+            titles: vec![series.title.clone()],
+            descriptions: vec![series.description.clone()],
+            tags: series
+                .keywords
+                .iter()
+                .chain(&series.season_tags)
+                .cloned()
+                .chain(series.categories.iter().map(|c| c.to_string()))
+                .collect(),
+            audio_locales: series.audio_locales.clone(),
+            subtitle_locales: series.subtitle_locales.clone(),
+            is_mature: series.is_mature,
+            is_premium_only: false,
+        }
+    }
+}
+
+impl From<&MovieListing> for IndexDocument {
+    fn from(movie_listing: &MovieListing) -> Self {
+        Self {
+            id: movie_listing.id.clone(),
+            media_type: IndexMediaType::MovieListing,
+            titles: vec![movie_listing.title.clone()],
+            descriptions: vec![movie_listing.description.clone()],
+            tags: movie_listing
+                .keywords
+                .iter()
+                .chain(&movie_listing.season_tags)
+                .cloned()
+                .chain(movie_listing.categories.iter().map(|c| c.to_string()))
+                .collect(),
+            audio_locales: movie_listing.audio_locale.clone().into_iter().collect(),
+            subtitle_locales: movie_listing.subtitle_locales.clone(),
+            is_mature: movie_listing.is_mature,
+            is_premium_only: movie_listing.is_premium_only,
+        }
+    }
+}
+
+impl From<&Episode> for IndexDocument {
+    fn from(episode: &Episode) -> Self {
+        Self {
+            id: episode.id.clone(),
+            media_type: IndexMediaType::Episode,
+            titles: vec![episode.title.clone(), episode.series_title.clone()],
+            descriptions: vec![episode.description.clone()],
+            tags: episode.season_tags.clone(),
+            audio_locales: vec![episode.audio_locale.clone()],
+            subtitle_locales: episode.subtitle_locales.clone(),
+            is_mature: episode.is_mature,
+            is_premium_only: episode.is_premium_only,
+        }
+    }
+}
+
+impl From<&MusicVideo> for IndexDocument {
+    fn from(music_video: &MusicVideo) -> Self {
+        Self {
+            id: music_video.id.clone(),
+            media_type: IndexMediaType::MusicVideo,
+            titles: vec![music_video.title.clone(), music_video.artist.name.clone()],
+            descriptions: vec![music_video.description.clone()],
+            tags: music_video
+                .genres
+                .iter()
+                .map(|g| g.display_value.clone())
+                .collect(),
+            audio_locales: vec![],
+            subtitle_locales: vec![],
+            is_mature: music_video.is_mature,
+            is_premium_only: music_video.is_premium_only,
+        }
+    }
+}
+
+/// Options for [`CatalogIndex::search`].
+#[derive(Clone, Debug)]
+pub struct IndexSearchOptions {
+    audio_locale: Option<Locale>,
+    include_mature: bool,
+    is_premium_only: Option<bool>,
+    limit: usize,
+}
+
+impl Default for IndexSearchOptions {
+    fn default() -> Self {
+        Self {
+            audio_locale: None,
+            include_mature: true,
+            is_premium_only: None,
+            limit: 20,
+        }
+    }
+}
+
+impl IndexSearchOptions {
+    /// Only return documents which are available in this audio locale.
+    pub fn audio_locale(mut self, audio_locale: Locale) -> Self {
+        self.audio_locale = Some(audio_locale);
+        self
+    }
+
+    /// Specifies whether mature documents should be returned. Default is `true`.
+    pub fn include_mature(mut self, include_mature: bool) -> Self {
+        self.include_mature = include_mature;
+        self
+    }
+
+    /// Only return documents which are (`true`) or aren't (`false`) premium only.
+    pub fn is_premium_only(mut self, is_premium_only: bool) -> Self {
+        self.is_premium_only = Some(is_premium_only);
+        self
+    }
+
+    /// Maximal number of returned results. Default is 20.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn matches(&self, document: &IndexDocument) -> bool {
+        if let Some(audio_locale) = &self.audio_locale {
+            if !document.audio_locales.contains(audio_locale) {
+                return false;
+            }
+        }
+        if let Some(is_premium_only) = self.is_premium_only {
+            if document.is_premium_only != is_premium_only {
+                return false;
+            }
+        }
+        self.include_mature || !document.is_mature
+    }
+}
+
+/// A result of [`CatalogIndex::search`].
+#[derive(Clone, Debug)]
+pub struct IndexSearchResult<'a> {
+    pub document: &'a IndexDocument,
+    /// Relevance of the document, higher is better.
+    pub score: f32,
+}
+
+/// Local full-text index of media metadata. Queries are matched against the titles, tags and
+/// descriptions of the indexed documents. Query words match words of the documents exactly, as
+/// prefix (only the last query word, to support searching while typing) or with up to one typo
+/// (two for words with 8 or more characters).
+#[derive(Clone, Debug, Default)]
+pub struct CatalogIndex {
+    documents: Vec<IndexDocument>,
+
+    /// Maps the document ids to their index in `documents`.
+    ids: HashMap<String, usize>,
+    /// Weights of the tokens of every document, in the same order as `documents`.
+    weights: Vec<HashMap<String, f32>>,
+    /// Maps every token to the documents which contains it.
+    postings: HashMap<String, HashSet<usize>>,
+}
+
+impl CatalogIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a document to the index. If a document with the same id is already indexed, both are
+    /// merged, so the same media requested in multiple locales can be found by the titles and
+    /// descriptions of all locales.
+    pub fn insert(&mut self, document: impl Into<IndexDocument>) {
+        let document = document.into();
+
+        let position = match self.ids.get(&document.id) {
+            Some(&position) => {
+                for token in self.weights[position].keys() {
+                    if let Some(posting) = self.postings.get_mut(token) {
+                        posting.remove(&position);
+                    }
+                }
+                self.documents[position].merge(document);
+                position
+            }
+            None => {
+                self.ids.insert(document.id.clone(), self.documents.len());
+                self.documents.push(document);
+                self.weights.push(HashMap::new());
+                self.documents.len() - 1
+            }
+        };
+
+        let weights = self.documents[position].token_weights();
+        for token in weights.keys() {
+            self.postings
+                .entry(token.clone())
+                .or_default()
+                .insert(position);
+        }
+        self.weights[position] = weights;
+    }
+
+    /// Return the document with id `id`.
+    pub fn get(&self, id: impl AsRef<str>) -> Option<&IndexDocument> {
+        self.ids.get(id.as_ref()).map(|&p| &self.documents[p])
+    }
+
+    /// All indexed documents.
+    pub fn documents(&self) -> &[IndexDocument] {
+        &self.documents
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Search the index for `query` and return the best matching documents, ordered by their
+    /// relevance.
+    pub fn search(
+        &self,
+        query: impl AsRef<str>,
+        options: &IndexSearchOptions,
+    ) -> Vec<IndexSearchResult<'_>> {
+        let query_tokens = tokenize(query.as_ref());
+        let document_count = self.documents.len() as f32;
+
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for (i, query_token) in query_tokens.iter().enumerate() {
+            let is_last = i == query_tokens.len() - 1;
+
+            // the best match factor of every document for this query token
+            let mut matches: HashMap<usize, (f32, &str)> = HashMap::new();
+            for (token, posting) in &self.postings {
+                let factor = if token == query_token {
+                    EXACT_MATCH_FACTOR
+                } else if is_last && token.starts_with(query_token.as_str()) {
+                    PREFIX_MATCH_FACTOR
+                } else if is_typo(query_token, token) {
+                    TYPO_MATCH_FACTOR
+                } else {
+                    continue;
+                };
+                let idf = (1.0 + document_count / posting.len() as f32).ln();
+
+                for &position in posting {
+                    let score = factor * idf;
+                    let entry = matches.entry(position).or_insert((0.0, token));
+                    if score > entry.0 {
+                        *entry = (score, token)
+                    }
+                }
+            }
+
+            for (position, (score, token)) in matches {
+                *scores.entry(position).or_default() += score * self.weights[position][token]
+            }
+        }
+
+        let mut results: Vec<IndexSearchResult> = scores
+            .into_iter()
+            .map(|(position, score)| IndexSearchResult {
+                document: &self.documents[position],
+                score,
+            })
+            .filter(|r| options.matches(r.document))
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.document.id.cmp(&b.document.id))
+        });
+        results.truncate(options.limit);
+        results
+    }
+
+    /// Write the index as json to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), &self.documents)?;
+        Ok(())
+    }
+
+    /// Read an index which was written with [`CatalogIndex::save`] from `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let documents: Vec<IndexDocument> = serde_json::from_reader(std::io::BufReader::new(file))?;
+
+        let mut index = Self::new();
+        for document in documents {
+            index.insert(document)
+        }
+        Ok(index)
+    }
+}
+
+/// Split `text` into lowercase words.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// If `token` is `query` with a few typos.
+fn is_typo(query: &str, token: &str) -> bool {
+    let max_distance = match query.chars().count() {
+        0..=3 => return false,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let (query_len, token_len) = (query.chars().count(), token.chars().count());
+    if query_len.abs_diff(token_len) > max_distance {
+        return false;
+    }
+    edit_distance(query, token) <= max_distance
+}
+
+/// Edit distance between `a` and `b` where a transposition of two adjacent characters counts as
+/// one edit ("optimal string alignment distance").
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1)
+            }
+            distances[i][j] = distance
+        }
+    }
+    distances[a.len()][b.len()]
+}
@@ -90,6 +90,15 @@ where
     Ok(Duration::milliseconds(i64::deserialize(deserializer)?))
 }
 
+pub(crate) fn deserialize_seconds_to_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Duration::milliseconds(
+        (f64::deserialize(deserializer)? * 1000.0) as i64,
+    ))
+}
+
 pub(crate) fn deserialize_try_from_string<'de, D, T: FromStr>(
     deserializer: D,
 ) -> Result<T, D::Error>
@@ -130,6 +139,25 @@ where
     }
 }
 
+/// Deserializes a empty object (`{}`) or `null` to `None`.
+pub(crate) fn deserialize_empty_object_to_none<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value: Value = Deserialize::deserialize(deserializer)?;
+
+    match &value {
+        Value::Null => Ok(None),
+        Value::Object(object) if object.is_empty() => Ok(None),
+        _ => serde_json::from_value(value)
+            .map(Some)
+            .map_err(|e| D::Error::custom(e.to_string())),
+    }
+}
+
 /// Deserializes a empty string (`""`) to `None`.
 pub(crate) fn deserialize_empty_pre_string_to_none<'de, D, T>(
     deserializer: D,
@@ -174,3 +202,51 @@ pub(crate) fn deserialize_streams_link<'de, D: Deserializer<'de>>(
         .ok_or_else(|| Error::custom("cannot extract stream id"))?
         .to_string())
 }
+
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_duration_to_millis<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_i64(duration.num_milliseconds())
+}
+
+/// Counterpart to [`deserialize_seconds_to_duration`].
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_duration_to_seconds<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.num_milliseconds() as f64 / 1000.0)
+}
+
+/// Counterpart to [`deserialize_try_from_string`].
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_to_string<S: serde::Serializer, T: ToString>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Counterpart to [`deserialize_empty_pre_string_to_none`].
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_none_to_empty_string<S: serde::Serializer, T: ToString>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.as_ref().map_or("".to_string(), |v| v.to_string()))
+}
+
+/// Counterpart to [`deserialize_thumbnail_image`].
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_thumbnail_image<S: serde::Serializer>(
+    images: &[Image],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry("thumbnail", &[images])?;
+    map.end()
+}
@@ -100,6 +100,42 @@
 //! }
 //! ```
 //!
+//! ## Serialization
+//!
+//! With the `serde` feature, all public data types implement [`serde::Serialize`] additionally to
+//! [`serde::Deserialize`], so you can store them e.g. as json in your own cache. A deserialized value
+//! has no session attached; use [`Crunchyroll::attach`] to attach one explicitly before calling
+//! functions which request the api.
+//!
+//! ```
+//! let json = serde_json::to_string(&episode)?;
+//!
+//! let episode: Episode = crunchy.attach(serde_json::from_str(&json)?).await;
+//! ```
+//!
+//! ## Offline search
+//!
+//! The `index` feature adds [`index::CatalogIndex`], a local full-text index of media metadata.
+//! It can be saved to / loaded from disk and searched without requesting the api.
+//!
+//! ```
+//! let mut index = CatalogIndex::new();
+//! index.insert(&series);
+//! index.save("index.json")?;
+//!
+//! let results = index.search("franxx", &IndexSearchOptions::default());
+//! ```
+//!
+//! ## SQLite export
+//!
+//! The `sqlite` feature adds [`sqlite::SqliteExporter`] which writes media into a normalized
+//! SQLite database. It can be used as sink of a [`crawler::Crawler`] to export the whole catalog.
+//!
+//! ```
+//! let mut exporter = SqliteExporter::open("catalog.db")?;
+//! crunchyroll.crawler().checkpoint("checkpoint.json").run(&mut exporter).await?;
+//! ```
+//!
 //! # Bugs
 //! Crunchyroll is awful in keep their api clean. Thus, some things are broken, will break for no
 //! reason or aren't well implemented (if at all). The methods added with the
@@ -114,19 +150,29 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod account;
+pub mod calendar;
 pub mod categories;
 pub mod common;
+pub mod crawler;
 pub mod crunchyroll;
 pub mod devices;
 pub mod error;
 pub mod feed;
+#[cfg(feature = "index")]
+#[cfg_attr(docsrs, doc(cfg(feature = "index")))]
+pub mod index;
 pub mod list;
+pub mod locale;
 pub mod media;
 #[cfg(feature = "parse")]
 #[cfg_attr(docsrs, doc(cfg(feature = "parse")))]
 pub mod parse;
 pub mod rating;
 pub mod search;
+#[cfg(feature = "sqlite")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
+pub mod sqlite;
+pub mod watcher;
 
 // internal
 mod internal;
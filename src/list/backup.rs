@@ -0,0 +1,672 @@
+use crate::error::CrunchyrollError;
+use crate::list::{SimpleWatchlistEntry, WatchlistEntry, WatchlistOptions};
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
+use crate::media::MediaType;
+use crate::search::{SearchOptions, SearchType};
+use crate::{Crunchyroll, MediaCollection, Result};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A series or movie listing on the watchlist or in a crunchylist. It's independent of the account
+/// it was exported from.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct BackupEntry {
+    /// Crunchyroll id of the series / movie listing. Might be empty if the entry was imported from
+    /// a format which doesn't contain Crunchyroll ids, the entry is matched by its title then.
+    pub id: String,
+    pub title: String,
+    /// Should only be [`MediaType::Series`] or [`MediaType::Movie`].
+    pub media_type: MediaType,
+
+    /// Only used for watchlist entries.
+    pub is_favorite: bool,
+    /// Only used for watchlist entries.
+    pub fully_watched: bool,
+    /// Only used for watchlist entries. See [`WatchlistEntry::in_progress`]. The progress itself
+    /// isn't part of the backup, so this isn't restored on import.
+    pub in_progress: bool,
+}
+
+impl BackupEntry {
+    fn from_media(media: &MediaCollection) -> Option<Self> {
+        match media {
+            MediaCollection::Series(series) => Some(Self {
+                id: series.id.clone(),
+                title: series.title.clone(),
+                media_type: MediaType::Series,
+                ..Default::default()
+            }),
+            MediaCollection::MovieListing(movie_listing) => Some(Self {
+                id: movie_listing.id.clone(),
+                title: movie_listing.title.clone(),
+                media_type: MediaType::Movie,
+                ..Default::default()
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A crunchylist with all its entries.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct BackupCrunchylist {
+    pub title: String,
+    pub entries: Vec<BackupEntry>,
+}
+
+/// Backup of the watchlist and all crunchylists of an account. Get it via
+/// [`Crunchyroll::export_lists`] and restore it (to the same or another account) via
+/// [`Crunchyroll::import_lists`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ListBackup {
+    pub watchlist: Vec<BackupEntry>,
+    pub crunchylists: Vec<BackupCrunchylist>,
+}
+
+const CSV_HEADER: [&str; 8] = [
+    "list_type",
+    "list_title",
+    "id",
+    "title",
+    "media_type",
+    "is_favorite",
+    "fully_watched",
+    "in_progress",
+];
+
+impl ListBackup {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json<S: AsRef<str>>(json: S) -> Result<Self> {
+        Ok(serde_json::from_str(json.as_ref())?)
+    }
+
+    /// Export as CSV ([RFC 4180](https://www.rfc-editor.org/rfc/rfc4180)). Every entry is a row,
+    /// the `list_type` column is either `watchlist` or `crunchylist` and `list_title` contains the
+    /// title of the crunchylist.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![CSV_HEADER.map(|h| h.to_string()).to_vec()];
+        let lists = std::iter::once(("watchlist", "", &self.watchlist)).chain(
+            self.crunchylists
+                .iter()
+                .map(|c| ("crunchylist", c.title.as_str(), &c.entries)),
+        );
+        for (list_type, list_title, entries) in lists {
+            for entry in entries {
+                rows.push(vec![
+                    list_type.to_string(),
+                    list_title.to_string(),
+                    entry.id.clone(),
+                    entry.title.clone(),
+                    entry.media_type.to_string(),
+                    entry.is_favorite.to_string(),
+                    entry.fully_watched.to_string(),
+                    entry.in_progress.to_string(),
+                ])
+            }
+        }
+
+        rows.into_iter()
+            .map(|row| {
+                let fields: Vec<String> = row.iter().map(|f| escape_csv(f)).collect();
+                format!("{}\r\n", fields.join(","))
+            })
+            .collect()
+    }
+
+    /// Import a CSV which was created with [`ListBackup::to_csv`].
+    pub fn from_csv<S: AsRef<str>>(csv: S) -> Result<Self> {
+        let mut rows = parse_csv(csv.as_ref()).into_iter();
+        let header = rows.next().unwrap_or_default();
+        let columns = CSV_HEADER
+            .iter()
+            .map(|c| header.iter().position(|h| h == c))
+            .collect::<Vec<Option<usize>>>();
+        // `in_progress` was added later, so csv files without it are still accepted
+        if let Some(i) = columns[..CSV_HEADER.len() - 1]
+            .iter()
+            .position(|c| c.is_none())
+        {
+            return Err(CrunchyrollError::Input(
+                format!("csv has no '{}' column", CSV_HEADER[i]).into(),
+            ));
+        }
+
+        let mut backup = ListBackup::default();
+        for row in rows {
+            let field = |i: usize| {
+                columns[i]
+                    .and_then(|c| row.get(c).cloned())
+                    .unwrap_or_default()
+            };
+            let entry = BackupEntry {
+                id: field(2),
+                title: field(3),
+                media_type: MediaType::from(field(4)),
+                is_favorite: field(5) == "true",
+                fully_watched: field(6) == "true",
+                in_progress: field(7) == "true",
+            };
+            match field(0).as_str() {
+                "watchlist" => backup.watchlist.push(entry),
+                "crunchylist" => {
+                    let title = field(1);
+                    match backup.crunchylists.iter_mut().find(|c| c.title == title) {
+                        Some(crunchylist) => crunchylist.entries.push(entry),
+                        None => backup.crunchylists.push(BackupCrunchylist {
+                            title,
+                            entries: vec![entry],
+                        }),
+                    }
+                }
+                list_type => {
+                    return Err(CrunchyrollError::Input(
+                        format!("invalid list type '{}'", list_type).into(),
+                    ))
+                }
+            }
+        }
+
+        Ok(backup)
+    }
+
+    /// Export the watchlist in the XML format which [MyAnimeList](https://myanimelist.net) uses
+    /// for its list import / export. MyAnimeList has no custom lists, so crunchylists aren't
+    /// exported. Fully watched entries have the status `Completed`, entries which are in progress
+    /// `Watching` and all others `Plan to Watch`. The Crunchyroll id is stored in the comments of
+    /// every entry, so it survives an export / import round trip.
+    ///
+    /// This is a round trip format only, to be imported again via [`ListBackup::from_mal_xml`].
+    /// Crunchyroll doesn't know the MyAnimeList ids of its series, so every entry has a
+    /// `series_animedb_id` of `0` and MyAnimeList itself can't import the file.
+    pub fn to_mal_xml(&self) -> String {
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8" ?>"#.to_string(),
+            "<myanimelist>".to_string(),
+            "\t<myinfo>".to_string(),
+            "\t\t<user_export_type>1</user_export_type>".to_string(),
+            format!(
+                "\t\t<user_total_anime>{}</user_total_anime>",
+                self.watchlist.len()
+            ),
+            "\t</myinfo>".to_string(),
+        ];
+        for entry in &self.watchlist {
+            let series_type = match entry.media_type {
+                MediaType::Movie => "Movie",
+                _ => "TV",
+            };
+            let status = if entry.fully_watched {
+                "Completed"
+            } else if entry.in_progress {
+                "Watching"
+            } else {
+                "Plan to Watch"
+            };
+            let tags = if entry.is_favorite { "favorite" } else { "" };
+            lines.extend([
+                "\t<anime>".to_string(),
+                "\t\t<series_animedb_id>0</series_animedb_id>".to_string(),
+                format!("\t\t<series_title>{}</series_title>", cdata(&entry.title)),
+                format!("\t\t<series_type>{}</series_type>", series_type),
+                format!("\t\t<my_status>{}</my_status>", status),
+                format!("\t\t<my_comments>{}</my_comments>", cdata(&entry.id)),
+                format!("\t\t<my_tags>{}</my_tags>", cdata(tags)),
+                "\t\t<update_on_import>1</update_on_import>".to_string(),
+                "\t</anime>".to_string(),
+            ])
+        }
+        lines.push("</myanimelist>".to_string());
+
+        lines.join("\n")
+    }
+
+    /// Import the watchlist from a MyAnimeList XML export. Entries of lists which weren't created
+    /// with [`ListBackup::to_mal_xml`] have no Crunchyroll id and are matched by their title when
+    /// imported. Entries with the status `Completed` are fully watched, `Watching` ones in
+    /// progress.
+    pub fn from_mal_xml<S: AsRef<str>>(xml: S) -> Result<Self> {
+        let xml = xml.as_ref();
+        if !xml.contains("<myanimelist>") {
+            return Err(CrunchyrollError::Input(
+                "xml is no myanimelist export".into(),
+            ));
+        }
+
+        let mut backup = ListBackup::default();
+        for block in xml.split("<anime>").skip(1) {
+            let block = block.split("</anime>").next().unwrap_or_default();
+            let media_type = match xml_value(block, "series_type").as_deref() {
+                Some("Movie") => MediaType::Movie,
+                _ => MediaType::Series,
+            };
+            let status = xml_value(block, "my_status");
+            // exports of myanimelist itself may contain arbitrary comments
+            let id = xml_value(block, "my_comments")
+                .filter(|comment| is_crunchyroll_id(comment))
+                .unwrap_or_default();
+            backup.watchlist.push(BackupEntry {
+                id,
+                title: xml_value(block, "series_title").unwrap_or_default(),
+                media_type,
+                is_favorite: xml_value(block, "my_tags")
+                    .unwrap_or_default()
+                    .split(',')
+                    .any(|t| t.trim() == "favorite"),
+                fully_watched: status.as_deref() == Some("Completed"),
+                in_progress: status.as_deref() == Some("Watching"),
+            })
+        }
+
+        Ok(backup)
+    }
+}
+
+/// If `id` looks like a Crunchyroll media id, e.g. `GY8VEQ95Y`.
+fn is_crunchyroll_id(id: &str) -> bool {
+    id.len() >= 9
+        && id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    quoted = false
+                }
+            }
+            ('"', false) => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => (),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row))
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row)
+    }
+
+    rows
+}
+
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Return the text of the first `tag` element in `xml`.
+fn xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", tag))?;
+    let value = xml[start..end].trim();
+
+    Some(if value.starts_with("<![CDATA[") {
+        // a cdata section can't contain `]]>`, so it's split into multiple sections
+        value
+            .split("<![CDATA[")
+            .map(|part| part.strip_suffix("]]>").unwrap_or(part))
+            .collect::<String>()
+    } else {
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    })
+}
+
+/// An entry of a [`ListBackup`] which could not be imported.
+#[derive(Clone, Debug)]
+pub struct UnmatchedEntry {
+    /// `watchlist` or the title of the crunchylist the entry belongs to.
+    pub list: String,
+    pub entry: BackupEntry,
+    pub reason: String,
+}
+
+/// Result of [`Crunchyroll::import_lists`].
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    /// Entries which were added to the watchlist. Entries which already were on the watchlist
+    /// aren't counted.
+    pub watchlist_added: u32,
+    pub favorites_restored: u32,
+    /// Entries whose episodes / movies were marked as watched because they were fully watched.
+    pub fully_watched_restored: u32,
+    pub crunchylists_created: u32,
+    /// Entries which were added to crunchylists. Entries which already were in the crunchylist
+    /// aren't counted.
+    pub crunchylist_entries_added: u32,
+
+    pub unmatched: Vec<UnmatchedEntry>,
+}
+
+impl Crunchyroll {
+    /// Export your watchlist and all your crunchylists.
+    pub async fn export_lists(&self) -> Result<ListBackup> {
+        let watchlist: Vec<WatchlistEntry> = self
+            .watchlist(WatchlistOptions::default())
+            .try_collect()
+            .await?;
+
+        let mut crunchylists = vec![];
+        for preview in self.crunchylists().await?.items {
+            let crunchylist = preview.crunchylist().await?;
+            crunchylists.push(BackupCrunchylist {
+                title: crunchylist.title,
+                entries: crunchylist
+                    .items
+                    .iter()
+                    .filter_map(|entry| BackupEntry::from_media(&entry.panel))
+                    .collect(),
+            })
+        }
+
+        Ok(ListBackup {
+            watchlist: watchlist
+                .iter()
+                .filter_map(|entry| {
+                    let mut backup_entry = BackupEntry::from_media(&entry.panel)?;
+                    backup_entry.is_favorite = entry.is_favorite;
+                    backup_entry.fully_watched = entry.fully_watched;
+                    backup_entry.in_progress = entry.in_progress();
+                    Some(backup_entry)
+                })
+                .collect(),
+            crunchylists,
+        })
+    }
+
+    /// Restore `backup`. Every entry is resolved by its Crunchyroll id or, if it has none or the id
+    /// doesn't exist (anymore), by its title. Entries are added to the watchlist and favorites are
+    /// restored, all episodes / movies of fully watched entries are marked as watched.
+    /// Crunchylists are created if no crunchylist with the same title exists. Everything which
+    /// could not be restored is listed in [`ImportReport::unmatched`]. An error is only returned
+    /// if requesting the entries by their ids fails for another reason than them not existing.
+    pub async fn import_lists(&self, backup: &ListBackup) -> Result<ImportReport> {
+        let mut report = ImportReport::default();
+
+        let all_entries = backup
+            .watchlist
+            .iter()
+            .chain(backup.crunchylists.iter().flat_map(|c| &c.entries));
+        let resolved = self.resolve_backup_entries(all_entries).await?;
+        let resolve = |list: &str, entry: &BackupEntry, report: &mut ImportReport| match resolved
+            .get(&(entry.id.clone(), entry.title.clone()))
+        {
+            Some(Ok(media)) => Some(media.clone()),
+            Some(Err(e)) => {
+                report.unmatched.push(UnmatchedEntry {
+                    list: list.to_string(),
+                    entry: entry.clone(),
+                    reason: e.to_string(),
+                });
+                None
+            }
+            None => {
+                report.unmatched.push(UnmatchedEntry {
+                    list: list.to_string(),
+                    entry: entry.clone(),
+                    reason: "no matching series or movie listing found".to_string(),
+                });
+                None
+            }
+        };
+        let unmatched_list =
+            |list: &BackupCrunchylist, reason: String, report: &mut ImportReport| {
+                report
+                    .unmatched
+                    .extend(list.entries.iter().map(|entry| UnmatchedEntry {
+                        list: list.title.clone(),
+                        entry: entry.clone(),
+                        reason: reason.clone(),
+                    }))
+            };
+
+        for entry in &backup.watchlist {
+            let media = match resolve("watchlist", entry, &mut report) {
+                Some(media) => media,
+                None => continue,
+            };
+            let result = restore_watchlist_entry(&media, entry, &mut report).await;
+            if let Err(e) = result {
+                report.unmatched.push(UnmatchedEntry {
+                    list: "watchlist".to_string(),
+                    entry: entry.clone(),
+                    reason: e.to_string(),
+                })
+            }
+        }
+
+        if backup.crunchylists.is_empty() {
+            return Ok(report);
+        }
+        let crunchylists = match self.crunchylists().await {
+            Ok(crunchylists) => crunchylists,
+            Err(e) => {
+                for backup_crunchylist in &backup.crunchylists {
+                    unmatched_list(backup_crunchylist, e.to_string(), &mut report)
+                }
+                return Ok(report);
+            }
+        };
+        for backup_crunchylist in &backup.crunchylists {
+            let preview = match crunchylists
+                .items
+                .iter()
+                .find(|c| c.title == backup_crunchylist.title)
+            {
+                Some(preview) => preview.clone(),
+                None => match crunchylists.create(&backup_crunchylist.title).await {
+                    Ok(preview) => {
+                        report.crunchylists_created += 1;
+                        preview
+                    }
+                    Err(e) => {
+                        unmatched_list(backup_crunchylist, e.to_string(), &mut report);
+                        continue;
+                    }
+                },
+            };
+            let crunchylist = match preview.crunchylist().await {
+                Ok(crunchylist) => crunchylist,
+                Err(e) => {
+                    unmatched_list(backup_crunchylist, e.to_string(), &mut report);
+                    continue;
+                }
+            };
+
+            for entry in &backup_crunchylist.entries {
+                let media = match resolve(&backup_crunchylist.title, entry, &mut report) {
+                    Some(media) => media,
+                    None => continue,
+                };
+                if crunchylist
+                    .items
+                    .iter()
+                    .any(|item| item.panel.id() == media.id())
+                {
+                    continue;
+                }
+                match crunchylist.add(media).await {
+                    Ok(_) => report.crunchylist_entries_added += 1,
+                    Err(e) => report.unmatched.push(UnmatchedEntry {
+                        list: backup_crunchylist.title.clone(),
+                        entry: entry.clone(),
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Resolve `entries` to their media. The key of the returned map is the id and title of the
+    /// entry, entries which could not be found aren't contained and entries whose title search
+    /// failed contain the error. Only entries without id or whose id doesn't exist are searched by
+    /// their title, other errors while requesting the ids are returned.
+    async fn resolve_backup_entries(
+        &self,
+        entries: impl Iterator<Item = &BackupEntry>,
+    ) -> Result<HashMap<(String, String), Result<MediaCollection>>> {
+        let mut resolved = HashMap::new();
+        let mut by_title = vec![];
+
+        let mut with_id = vec![];
+        for entry in entries {
+            if entry.id.is_empty() {
+                by_title.push(entry)
+            } else {
+                with_id.push(entry)
+            }
+        }
+        let ids: Vec<&str> = with_id.iter().map(|e| e.id.as_str()).collect();
+        for (entry, media) in with_id
+            .into_iter()
+            .zip(MediaCollection::from_ids(self, &ids).await)
+        {
+            match media {
+                Ok(media) => {
+                    resolved.insert((entry.id.clone(), entry.title.clone()), Ok(media));
+                }
+                // the id doesn't exist (anymore)
+                Err(CrunchyrollError::Input(_)) => by_title.push(entry),
+                Err(e) => return Err(e),
+            }
+        }
+
+        for entry in by_title {
+            let key = (entry.id.clone(), entry.title.clone());
+            if resolved.contains_key(&key) {
+                continue;
+            }
+            let search_type = match entry.media_type {
+                MediaType::Movie => SearchType::MovieListing,
+                _ => SearchType::Series,
+            };
+            let results: Vec<MediaCollection> = match self
+                .search(
+                    &entry.title,
+                    SearchOptions::default()
+                        .types(vec![search_type])
+                        .page_size(10),
+                )
+                .take(10)
+                .try_collect()
+                .await
+            {
+                Ok(results) => results,
+                Err(e) => {
+                    resolved.insert(key, Err(e));
+                    continue;
+                }
+            };
+            let found = results.into_iter().find(|media| {
+                BackupEntry::from_media(media)
+                    .map(|e| e.title.to_lowercase() == entry.title.to_lowercase())
+                    .unwrap_or_default()
+            });
+            if let Some(media) = found {
+                resolved.insert(key, Ok(media));
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Add `media` to the watchlist if it isn't already on it and restore its favorite and fully
+/// watched state.
+async fn restore_watchlist_entry(
+    media: &MediaCollection,
+    entry: &BackupEntry,
+    report: &mut ImportReport,
+) -> Result<()> {
+    let mut watchlist_entry = simple_watchlist_entry(media).await?;
+    if watchlist_entry.is_none() {
+        match media {
+            MediaCollection::Series(series) => series.add_to_watchlist().await?,
+            MediaCollection::MovieListing(movie_listing) => {
+                movie_listing.add_to_watchlist().await?
+            }
+            _ => return Ok(()),
+        }
+        report.watchlist_added += 1;
+        if entry.is_favorite {
+            watchlist_entry = simple_watchlist_entry(media).await?
+        }
+    }
+
+    if let Some(mut watchlist_entry) = watchlist_entry {
+        if entry.is_favorite && !watchlist_entry.is_favorite {
+            watchlist_entry.mark_favorite(true).await?;
+            report.favorites_restored += 1
+        }
+    }
+
+    if entry.fully_watched {
+        mark_watched(media).await?;
+        report.fully_watched_restored += 1
+    }
+    Ok(())
+}
+
+/// Mark all episodes / movies of `media` as watched. The first failed update is returned as error.
+async fn mark_watched(media: &MediaCollection) -> Result<()> {
+    let results: Vec<Result<()>> = match media {
+        MediaCollection::Series(series) => series
+            .mark_watched()
+            .await?
+            .into_iter()
+            .map(|(_, result)| result)
+            .collect(),
+        MediaCollection::MovieListing(movie_listing) => {
+            let movies = movie_listing.movies().await?;
+            stream::iter(movies.iter().map(|movie| movie.mark_watched()))
+                .buffered(MAX_CONCURRENT_REQUESTS)
+                .collect()
+                .await
+        }
+        _ => vec![],
+    };
+    results.into_iter().collect()
+}
+
+async fn simple_watchlist_entry(media: &MediaCollection) -> Result<Option<SimpleWatchlistEntry>> {
+    match media {
+        MediaCollection::Series(series) => series.into_watchlist_entry().await,
+        MediaCollection::MovieListing(movie_listing) => movie_listing.into_watchlist_entry().await,
+        _ => Ok(None),
+    }
+}
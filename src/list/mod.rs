@@ -1,7 +1,9 @@
+mod backup;
 mod crunchylist;
 mod watch_history;
 mod watchlist;
 
+pub use backup::*;
 pub use crunchylist::*;
 pub use watch_history::*;
 pub use watchlist::*;
@@ -1,8 +1,11 @@
 use crate::common::{Pagination, V2BulkResult};
-use crate::{Crunchyroll, EmptyJsonProxy, MediaCollection, Request, Result};
-use chrono::{DateTime, Utc};
-use futures_util::FutureExt;
+use crate::error::CrunchyrollError;
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
+use crate::{Crunchyroll, EmptyJsonProxy, Episode, MediaCollection, Request, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures_util::{stream, FutureExt, StreamExt};
 use serde::Deserialize;
+use std::collections::HashSet;
 
 /// Entry of your watchlist.
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
@@ -23,6 +26,23 @@ pub struct WatchHistoryEntry {
     pub panel: MediaCollection,
 }
 
+/// What to watch next in a series / movie listing. See [`Crunchyroll::continue_watching`].
+#[derive(Clone, Debug)]
+pub struct ContinueWatchingEntry {
+    /// Id of the series / movie listing the media belongs to.
+    pub parent_id: String,
+    /// Either [`MediaCollection::Episode`] or [`MediaCollection::Movie`]. Episodes are in your
+    /// preferred audio locale if one is set and the episode is available in it.
+    pub media: MediaCollection,
+    /// If the media was already started. If `false`, it's the next unwatched media after the
+    /// last fully watched one.
+    pub in_progress: bool,
+    /// Position in seconds where watching should be resumed.
+    pub playhead: u32,
+    /// Watch progress of the media in percent (`0.0` - `100.0`).
+    pub progress: f64,
+}
+
 impl Crunchyroll {
     /// Get the history which episodes / movies you've watched.
     pub fn watch_history(&self) -> Pagination<WatchHistoryEntry> {
@@ -63,4 +83,128 @@ impl Crunchyroll {
             .await?;
         Ok(())
     }
+
+    /// Return what to watch next, for at most `limit` series / movie listings, based on your watch
+    /// history. For every series / movie listing the most recently watched episode / movie is
+    /// taken: if it isn't fully watched, it can be resumed, otherwise the next episode / movie is
+    /// returned. Series / movie listings which are watched completely, or whose next episode /
+    /// movie doesn't exist (anymore), are skipped and further watch history is used instead. The
+    /// entries are ordered by when they were last watched. Other errors, e.g. if the session
+    /// expired or the account got rate limited, are returned instead of a shortened list.
+    pub async fn continue_watching(&self, limit: usize) -> Result<Vec<ContinueWatchingEntry>> {
+        let mut entries = vec![];
+        let mut seen = HashSet::new();
+        let mut history = self.watch_history();
+        let mut history_exhausted = false;
+        while entries.len() < limit && !history_exhausted {
+            // take as many new series / movie listings from the history as entries are missing and
+            // resolve them concurrently
+            let mut latest = vec![];
+            while latest.len() < limit - entries.len() {
+                let entry = match history.next().await {
+                    Some(entry) => entry?,
+                    None => {
+                        history_exhausted = true;
+                        break;
+                    }
+                };
+                let parent_id = if !entry.parent_id.is_empty() {
+                    entry.parent_id.clone()
+                } else {
+                    match &entry.panel {
+                        MediaCollection::Episode(episode) => episode.series_id.clone(),
+                        MediaCollection::Movie(movie) => movie.movie_listing_id.clone(),
+                        _ => continue,
+                    }
+                };
+                if seen.insert(parent_id.clone()) {
+                    latest.push((parent_id, entry))
+                }
+            }
+
+            let resolved: Vec<Result<Option<ContinueWatchingEntry>>> = stream::iter(
+                latest
+                    .into_iter()
+                    .map(|(parent_id, entry)| self.continue_watching_entry(parent_id, entry)),
+            )
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+            for entry in resolved {
+                match entry {
+                    Ok(Some(entry)) => entries.push(entry),
+                    // nothing to watch next or the media doesn't exist (anymore)
+                    Ok(None) | Err(CrunchyrollError::Input(_)) => (),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn continue_watching_entry(
+        &self,
+        parent_id: String,
+        entry: WatchHistoryEntry,
+    ) -> Result<Option<ContinueWatchingEntry>> {
+        let in_progress = !entry.fully_watched;
+        let (media, playhead, duration): (MediaCollection, u32, Duration) = match entry.panel {
+            MediaCollection::Episode(episode) => {
+                let (episode, playhead) = if in_progress {
+                    (episode, entry.playhead)
+                } else if let Some(next) = episode.next().await? {
+                    (next.media, next.playhead)
+                } else {
+                    return Ok(None);
+                };
+                let episode = self.preferred_audio_version(episode).await?;
+                let duration = episode.duration;
+                (episode.into(), playhead, duration)
+            }
+            MediaCollection::Movie(movie) => {
+                let (movie, playhead) = if in_progress {
+                    (movie, entry.playhead)
+                } else if let Some(next) = movie.next().await? {
+                    (next.media, next.playhead)
+                } else {
+                    return Ok(None);
+                };
+                let duration = movie.duration;
+                (movie.into(), playhead, duration)
+            }
+            _ => return Ok(None),
+        };
+
+        let progress = if duration.num_seconds() > 0 {
+            (playhead as f64 / duration.num_seconds() as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        Ok(Some(ContinueWatchingEntry {
+            parent_id,
+            media,
+            in_progress,
+            playhead,
+            progress,
+        }))
+    }
+
+    /// Return the version of `episode` in the preferred audio locale if one is set and the episode
+    /// is available in it, otherwise `episode` itself.
+    async fn preferred_audio_version(&self, mut episode: Episode) -> Result<Episode> {
+        if let Some(locale) = &self.executor.details.preferred_audio_locale {
+            if &episode.audio_locale != locale {
+                if let Some(version) = episode
+                    .version(vec![locale.clone()])
+                    .await?
+                    .into_iter()
+                    .next()
+                {
+                    return Ok(version);
+                }
+            }
+        }
+        Ok(episode)
+    }
 }
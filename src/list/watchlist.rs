@@ -1,14 +1,16 @@
-use crate::common::V2BulkResult;
+use crate::common::{Pagination, V2BulkResult};
 use crate::error::CrunchyrollError;
 use crate::{
     enum_values, options, Crunchyroll, EmptyJsonProxy, Executor, MediaCollection, Request, Result,
 };
 use chrono::{DateTime, Utc};
+use futures_util::FutureExt;
 use serde::Deserialize;
 use serde_json::json;
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[request(executor(panel))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -23,6 +25,8 @@ pub struct WatchlistEntry {
     pub never_watched: bool,
     pub fully_watched: bool,
 
+    /// Position (in seconds) in the episode / movie you're currently watching or should watch next
+    /// in this series / movie listing.
     pub playhead: u32,
 
     /// Should only be [`MediaCollection::Series`] or [`MediaCollection::MovieListing`].
@@ -45,6 +49,11 @@ impl WatchlistEntry {
         remove_from_watchlist(self.executor, id).await
     }
 
+    /// If you started watching this entry but haven't finished it yet.
+    pub fn in_progress(&self) -> bool {
+        !self.never_watched && !self.fully_watched
+    }
+
     /// Get the media id of the series / movie listing which represents this entry.
     fn get_id(&self) -> Result<String> {
         match self.panel.clone() {
@@ -116,38 +125,87 @@ options! {
     order(WatchlistOrder, "order") = Some(WatchlistOrder::Newest),
     sort(WatchlistSort, "sort_by") = None,
     media_type(crate::media::MediaType, "type") = None,
+    /// [`WatchlistLanguage::Custom`] values are sent as they are.
     language(WatchlistLanguage, "language") = None,
-    only_favorites(bool, "only_favorites") = Some(false)
+    only_favorites(bool, "only_favorites") = Some(false),
+    /// Only return entries which have (`true`) or haven't (`false`) new episodes.
+    new(bool, "new") = None,
+    /// Only return entries which were (`true`) or weren't (`false`) fully watched.
+    fully_watched(bool, "fully_watched") = None
 }
 
 impl Crunchyroll {
     /// Returns your watchlist.
-    pub async fn watchlist(&self, mut options: WatchlistOptions) -> Result<Vec<WatchlistEntry>> {
-        let true_string = true.to_string();
-        let language_field = if let Some(language) = options.language {
-            match language {
-                WatchlistLanguage::Subbed => ("is_subbed", true_string.as_str()),
-                WatchlistLanguage::Dubbed => ("is_dubbed", true_string.as_str()),
-                _ => ("", ""),
-            }
-        } else {
-            ("", "")
-        };
-        options.language = None;
-
-        let endpoint = format!(
-            "https://www.crunchyroll.com/content/v2/discover/{}/watchlist",
-            self.executor.details.account_id.clone()?
-        );
-        Ok(self
-            .executor
-            .get(endpoint)
-            .query(&options.into_query())
-            .query(&[language_field])
-            .apply_locale_query()
-            .request::<V2BulkResult<WatchlistEntry>>()
-            .await?
-            .data)
+    ///
+    /// If [`WatchlistOptions::new`] or [`WatchlistOptions::fully_watched`] is set, the entries are
+    /// filtered locally because the api doesn't support these filters. Because of this, pages may
+    /// contain fewer entries than the page size and [`Pagination::total`] is the unfiltered total
+    /// of the whole watchlist until the last page was requested. The offset of every request is
+    /// calculated from the page number and page size, so the page size must not be changed via
+    /// [`Pagination::page_size`] after the first entry was received, otherwise entries get skipped
+    /// or returned twice.
+    pub fn watchlist(&self, options: WatchlistOptions) -> Pagination<WatchlistEntry> {
+        Pagination::new(
+            |options| {
+                async move {
+                    let endpoint = format!(
+                        "https://www.crunchyroll.com/content/v2/discover/{}/watchlist",
+                        options.executor.details.account_id.clone()?
+                    );
+
+                    let mut query = vec![];
+                    let mut new = None;
+                    let mut fully_watched = None;
+                    for (key, value) in options.query.clone() {
+                        match (key.as_str(), WatchlistLanguage::from(value.clone())) {
+                            ("language", WatchlistLanguage::Subbed) => {
+                                query.push(("is_subbed".to_string(), true.to_string()))
+                            }
+                            ("language", WatchlistLanguage::Dubbed) => {
+                                query.push(("is_dubbed".to_string(), true.to_string()))
+                            }
+                            ("new", _) => new = Some(value == "true"),
+                            ("fully_watched", _) => fully_watched = Some(value == "true"),
+                            _ => query.push((key, value)),
+                        }
+                    }
+
+                    // the new and fully watched filters are applied locally, so the offset must be
+                    // calculated from the page and not from the count of already returned entries
+                    let start = (options.page - 1) * options.page_size;
+                    let result = options
+                        .executor
+                        .get(endpoint)
+                        .query(&query)
+                        .query(&[("n", options.page_size), ("start", start)])
+                        .apply_locale_query()
+                        .request::<V2BulkResult<WatchlistEntry>>()
+                        .await?;
+
+                    let is_last_page = result.data.len() < options.page_size as usize
+                        || start + options.page_size >= result.total;
+                    let entries: Vec<WatchlistEntry> = result
+                        .data
+                        .into_iter()
+                        .filter(|e| new.is_none() || new == Some(e.new))
+                        .filter(|e| {
+                            fully_watched.is_none() || fully_watched == Some(e.fully_watched)
+                        })
+                        .collect();
+                    // the total doesn't consider the locally applied filters, so it's only correct
+                    // once the last page was requested
+                    if is_last_page {
+                        let total = options.start + entries.len() as u32;
+                        return Ok((entries, total));
+                    }
+                    Ok((entries, result.total))
+                }
+                .boxed()
+            },
+            self.executor.clone(),
+            Some(options.into_query()),
+            None,
+        )
     }
 }
 
@@ -0,0 +1,320 @@
+//! Locale parsing, matching and preferences.
+
+use crate::Locale;
+
+/// Languages [`Locale::display_name`] can return names in. The order must match the order of
+/// the names returned by [`display_names`].
+const DISPLAY_NAME_LANGUAGES: [&str; 7] = ["en", "de", "fr", "es", "pt", "it", "ja"];
+
+fn display_names(locale: &Locale) -> Option<[&'static str; 7]> {
+    Some(match locale {
+        Locale::ar_ME => [
+            "Arabic",
+            "Arabisch",
+            "Arabe",
+            "Árabe",
+            "Árabe",
+            "Arabo",
+            "アラビア語",
+        ],
+        Locale::ar_SA => [
+            "Arabic (Saudi Arabia)",
+            "Arabisch (Saudi-Arabien)",
+            "Arabe (Arabie saoudite)",
+            "Árabe (Arabia Saudí)",
+            "Árabe (Arábia Saudita)",
+            "Arabo (Arabia Saudita)",
+            "アラビア語 (サウジアラビア)",
+        ],
+        Locale::de_DE => [
+            "German",
+            "Deutsch",
+            "Allemand",
+            "Alemán",
+            "Alemão",
+            "Tedesco",
+            "ドイツ語",
+        ],
+        Locale::en_IN => [
+            "English (India)",
+            "Englisch (Indien)",
+            "Anglais (Inde)",
+            "Inglés (India)",
+            "Inglês (Índia)",
+            "Inglese (India)",
+            "英語 (インド)",
+        ],
+        Locale::en_US => [
+            "English (US)",
+            "Englisch (USA)",
+            "Anglais (États-Unis)",
+            "Inglés (EE. UU.)",
+            "Inglês (EUA)",
+            "Inglese (USA)",
+            "英語 (アメリカ)",
+        ],
+        Locale::es_419 | Locale::es_LA => [
+            "Spanish (Latin America)",
+            "Spanisch (Lateinamerika)",
+            "Espagnol (Amérique latine)",
+            "Español (América Latina)",
+            "Espanhol (América Latina)",
+            "Spagnolo (America Latina)",
+            "スペイン語 (ラテンアメリカ)",
+        ],
+        Locale::es_ES => [
+            "Spanish (European)",
+            "Spanisch (Europa)",
+            "Espagnol (Europe)",
+            "Español (Europa)",
+            "Espanhol (Europa)",
+            "Spagnolo (Europa)",
+            "スペイン語 (ヨーロッパ)",
+        ],
+        Locale::fr_FR => [
+            "French",
+            "Französisch",
+            "Français",
+            "Francés",
+            "Francês",
+            "Francese",
+            "フランス語",
+        ],
+        Locale::hi_IN => [
+            "Hindi",
+            "Hindi",
+            "Hindi",
+            "Hindi",
+            "Hindi",
+            "Hindi",
+            "ヒンディー語",
+        ],
+        Locale::it_IT => [
+            "Italian",
+            "Italienisch",
+            "Italien",
+            "Italiano",
+            "Italiano",
+            "Italiano",
+            "イタリア語",
+        ],
+        Locale::ja_JP => [
+            "Japanese",
+            "Japanisch",
+            "Japonais",
+            "Japonés",
+            "Japonês",
+            "Giapponese",
+            "日本語",
+        ],
+        Locale::pt_BR => [
+            "Portuguese (Brazil)",
+            "Portugiesisch (Brasilien)",
+            "Portugais (Brésil)",
+            "Portugués (Brasil)",
+            "Português (Brasil)",
+            "Portoghese (Brasile)",
+            "ポルトガル語 (ブラジル)",
+        ],
+        Locale::pt_PT => [
+            "Portuguese (Europe)",
+            "Portugiesisch (Europa)",
+            "Portugais (Europe)",
+            "Portugués (Europa)",
+            "Português (Europa)",
+            "Portoghese (Europa)",
+            "ポルトガル語 (ヨーロッパ)",
+        ],
+        Locale::ru_RU => [
+            "Russian",
+            "Russisch",
+            "Russe",
+            "Ruso",
+            "Russo",
+            "Russo",
+            "ロシア語",
+        ],
+        Locale::zh_CN => [
+            "Chinese (China)",
+            "Chinesisch (China)",
+            "Chinois (Chine)",
+            "Chino (China)",
+            "Chinês (China)",
+            "Cinese (Cina)",
+            "中国語 (中国)",
+        ],
+        Locale::Custom(_) => return None,
+    })
+}
+
+/// Locale which is used if only a language without region is given to [`Locale::from_bcp47`].
+fn default_for_language(language: &str) -> Option<Locale> {
+    Some(match language {
+        "ar" => Locale::ar_SA,
+        "de" => Locale::de_DE,
+        "en" => Locale::en_US,
+        "es" => Locale::es_419,
+        "fr" => Locale::fr_FR,
+        "hi" => Locale::hi_IN,
+        "it" => Locale::it_IT,
+        "ja" => Locale::ja_JP,
+        "pt" => Locale::pt_BR,
+        "ru" => Locale::ru_RU,
+        "zh" => Locale::zh_CN,
+        _ => return None,
+    })
+}
+
+/// How well two locales match. See [`Locale::matches`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LocaleMatch {
+    NoMatch,
+    /// Same language but different (or no) region, e.g. `de-DE` and `de-AT`.
+    Language,
+    /// Same language and region.
+    Exact,
+}
+
+impl Locale {
+    /// Parse a [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag. The tag is
+    /// normalized before parsing, so `en_us`, `EN-US` and `en-Latn-US` all result in
+    /// [`Locale::en_US`]. A tag without region results in the locale which Crunchyroll uses for
+    /// the language (e.g. `de` results in [`Locale::de_DE`]). Tags which aren't supported by
+    /// Crunchyroll result in a normalized [`Locale::Custom`].
+    pub fn from_bcp47(tag: impl AsRef<str>) -> Locale {
+        let tag = tag.as_ref().trim().replace('_', "-");
+        let mut subtags = tag.split('-').filter(|s| !s.is_empty());
+
+        let language = match subtags.next() {
+            Some(language) => language.to_lowercase(),
+            None => return Locale::Custom("".to_string()),
+        };
+        let region = subtags
+            // skip the script subtag, e.g. `Latn`
+            .find(|s| {
+                (s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(|s| s.to_uppercase());
+
+        match region {
+            Some(region) => Locale::from(format!("{}-{}", language, region)),
+            None => default_for_language(&language).unwrap_or(Locale::Custom(language)),
+        }
+    }
+
+    /// The (lowercase) language subtag of the locale, e.g. `en` for [`Locale::en_US`].
+    pub fn language(&self) -> String {
+        self.to_string()
+            .split('-')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    }
+
+    /// The (uppercase) region subtag of the locale, e.g. `US` for [`Locale::en_US`]. [`None`] if
+    /// the locale has no region. [`Locale::es_LA`] has the same region as [`Locale::es_419`]
+    /// (`419`, Latin America).
+    pub fn region(&self) -> Option<String> {
+        let region = self
+            .to_string()
+            .split('-')
+            .skip(1)
+            .find(|s| s.len() == 2 || s.len() == 3)?
+            .to_uppercase();
+        if region == "LA" {
+            Some("419".to_string())
+        } else {
+            Some(region)
+        }
+    }
+
+    /// Compare the language and region of this locale with `other`.
+    pub fn matches(&self, other: &Locale) -> LocaleMatch {
+        if self.language() != other.language() || self.language().is_empty() {
+            LocaleMatch::NoMatch
+        } else if self.region() == other.region() {
+            LocaleMatch::Exact
+        } else {
+            LocaleMatch::Language
+        }
+    }
+
+    /// Name of the locale in the language of `ui_locale`. Names are available in English, German,
+    /// French, Spanish, Portuguese, Italian and Japanese, for any other language the English name
+    /// is returned. [`Locale::Custom`] returns its raw value.
+    pub fn display_name(&self, ui_locale: &Locale) -> String {
+        let names = match display_names(self) {
+            Some(names) => names,
+            None => return self.to_string(),
+        };
+        let ui_language = ui_locale.language();
+        let index = DISPLAY_NAME_LANGUAGES
+            .iter()
+            .position(|l| *l == ui_language)
+            .unwrap_or_default();
+        names[index].to_string()
+    }
+}
+
+/// An ordered list of preferred locales, e.g. "prefer `de-DE`, then `en-US`, then `ja-JP`". It's
+/// used to pick between multiple available locales, like audio versions or subtitles. For every
+/// preferred locale, in order, an exact match is searched first and a match with the same
+/// language but a different region afterwards (see [`Locale::matches`]), so `de-DE` is preferred
+/// over `de-AT` but `de-AT` is still preferred over `en-US`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LocalePreference {
+    locales: Vec<Locale>,
+}
+
+impl LocalePreference {
+    pub fn new(locales: Vec<Locale>) -> Self {
+        Self { locales }
+    }
+
+    /// Append `locale` as the least preferred locale.
+    pub fn then(mut self, locale: Locale) -> Self {
+        self.locales.push(locale);
+        self
+    }
+
+    /// All preferred locales, the most preferred first.
+    pub fn locales(&self) -> &[Locale] {
+        &self.locales
+    }
+
+    /// Return the best match of `available`. [`None`] if no locale matches any preferred locale.
+    pub fn best_match<'a>(
+        &self,
+        available: impl IntoIterator<Item = &'a Locale>,
+    ) -> Option<&'a Locale> {
+        self.select(available, |l| l)
+    }
+
+    /// Return the item of `items` whose locale (which is extracted with `locale`) matches best.
+    /// [`None`] if no item matches any preferred locale.
+    pub fn select<'a, T>(
+        &self,
+        items: impl IntoIterator<Item = &'a T>,
+        locale: impl Fn(&T) -> &Locale,
+    ) -> Option<&'a T> {
+        let items: Vec<&T> = items.into_iter().collect();
+        for preferred in &self.locales {
+            for match_level in [LocaleMatch::Exact, LocaleMatch::Language] {
+                if let Some(item) = items
+                    .iter()
+                    .find(|item| preferred.matches(locale(item)) == match_level)
+                {
+                    return Some(item);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl From<Vec<Locale>> for LocalePreference {
+    fn from(locales: Vec<Locale>) -> Self {
+        Self::new(locales)
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub(crate) struct EpisodeVersion {
@@ -31,6 +32,7 @@ pub(crate) struct EpisodeVersion {
 /// Metadata for a episode.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(remote = "Self")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -86,6 +88,10 @@ pub struct Episode {
 
     #[serde(alias = "duration_ms")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub duration: Duration,
 
@@ -104,6 +110,10 @@ pub struct Episode {
     pub availability_ends: DateTime<Utc>,
 
     #[serde(deserialize_with = "crate::internal::serde::deserialize_thumbnail_image")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_thumbnail_image")
+    )]
     pub images: Vec<Image>,
 
     pub is_dubbed: bool,
@@ -199,6 +209,10 @@ impl Media for Episode {
         .remove(0))
     }
 
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/cms/objects"
+    }
+
     #[cfg(feature = "experimental-stabilizations")]
     async fn __apply_experimental_stabilizations(&mut self) {
         if self.executor.fixes.locale_name_parsing {
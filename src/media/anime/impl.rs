@@ -1,35 +1,76 @@
 use crate::common::Request;
 use crate::media::Media;
 use crate::{Episode, MediaCollection, Movie, MovieListing, Result, Season, Series};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::de::{DeserializeOwned, Error};
 use serde::{Deserialize, Deserializer};
 use serde_json::Map;
 
+/// A segment of an episode / movie which can be skipped. See [`SkipEvents`].
 #[allow(dead_code)]
-#[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
-pub(crate) struct VideoIntroResult {
-    pub(crate) media_id: String,
-
-    #[serde(rename = "startTime")]
-    pub(crate) start_time: f64,
-    #[serde(rename = "endTime")]
-    pub(crate) end_time: f64,
-    pub(crate) duration: f64,
+pub struct SkipEvent {
+    /// Time after the beginning of the episode / movie when the segment starts.
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_seconds_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_seconds")
+    )]
+    #[default(Duration::zero())]
+    pub start: Duration,
+    /// Time after the beginning of the episode / movie when the segment ends.
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_seconds_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_seconds")
+    )]
+    #[default(Duration::zero())]
+    pub end: Duration,
 
-    /// Id of the next episode.
-    #[serde(rename = "comparedWith")]
-    pub(crate) compared_with: String,
+    #[cfg(feature = "__test_strict")]
+    approver_id: Option<crate::StrictValue>,
+    #[cfg(feature = "__test_strict")]
+    distribution_number: Option<crate::StrictValue>,
+    #[cfg(feature = "__test_strict")]
+    title: Option<crate::StrictValue>,
+    #[cfg(feature = "__test_strict")]
+    series: Option<crate::StrictValue>,
+    #[cfg(feature = "__test_strict")]
+    #[serde(rename = "type")]
+    type_: Option<crate::StrictValue>,
+    #[cfg(feature = "__test_strict")]
+    subtype: Option<crate::StrictValue>,
+}
 
-    /// It seems that this represents the episode number relative to the season the episode is part
-    /// of. But in a weird way. It is, for example, '0003.00' instead of simply 3 if it's the third
-    /// episode in a season.
-    pub(crate) ordering: String,
+/// Segments of an episode / movie which can be skipped, like the intro or credits. A segment is
+/// [`None`] if the episode / movie doesn't have it (or Crunchyroll has no data about it).
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
+#[cfg_attr(not(feature = "__test_strict"), serde(default))]
+pub struct SkipEvents {
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_empty_object_to_none")]
+    pub intro: Option<SkipEvent>,
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_empty_object_to_none")]
+    pub credits: Option<SkipEvent>,
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_empty_object_to_none")]
+    pub recap: Option<SkipEvent>,
+    /// Preview of the next episode, mostly shown after the credits.
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::internal::serde::deserialize_empty_object_to_none")]
+    pub preview: Option<SkipEvent>,
 
-    #[default(DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH))]
-    pub(crate) last_updated: DateTime<Utc>,
+    #[cfg(feature = "__test_strict")]
+    media_id: crate::StrictValue,
 }
 
 #[allow(dead_code)]
@@ -64,20 +105,23 @@ where
         .map_err(|e| Error::custom(e.to_string()))
 }
 
-#[allow(dead_code)]
+/// Watch progress of an episode / movie.
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct PlayheadInformation {
-    playhead: u32,
+    /// Position in seconds where the episode / movie was last watched.
+    pub playhead: u32,
 
-    content_id: String,
+    /// Id of the episode / movie.
+    pub content_id: String,
 
-    fully_watched: bool,
+    pub fully_watched: bool,
 
     /// Date when the last playhead update was
     #[default(DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH))]
-    last_modified: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
 }
 
 macro_rules! impl_manual_media_deserialize {
@@ -120,6 +164,27 @@ impl_manual_media_deserialize! {
     Movie = "movie_metadata"
 }
 
+#[cfg(feature = "serde")]
+macro_rules! impl_manual_media_serialize {
+    ($($media:ident)*) => {
+        $(
+            impl serde::Serialize for $media {
+                fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    $media::serialize(self, serializer)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "serde")]
+impl_manual_media_serialize! {
+    Series Season Episode MovieListing Movie
+}
+
 macro_rules! impl_media_request {
     ($($media:ident)*) => {
         $(
@@ -195,6 +260,22 @@ macro_rules! media_version {
                     Ok(result)
                 }
 
+                /// Get the version whose audio locale matches `preference` best. This is the media
+                /// itself if its audio locale matches best. [`None`] if no version matches.
+                pub async fn preferred_version(&mut self, preference: &$crate::locale::LocalePreference) -> Result<Option<$media>> {
+                    self.assert_versions().await?;
+                    let version_id = match preference.select(self.versions.as_ref().unwrap(), |v| &v.audio_locale) {
+                        Some(version) => version.id.clone(),
+                        None => return Ok(None)
+                    };
+
+                    if version_id == self.id {
+                        Ok(Some(self.clone()))
+                    } else {
+                        Ok(Some($media::from_id(&$crate::Crunchyroll { executor: self.executor.clone() }, version_id).await?))
+                    }
+                }
+
                 #[doc=$versions_doc]
                 pub async fn versions(&mut self) -> Result<Vec<$media>> {
                     self.assert_versions().await?;
@@ -272,22 +353,32 @@ macro_rules! impl_media_video {
                     self.executor.details.premium || !self.is_premium_only
                 }
 
-                /// Get time _in seconds_ when the episode / movie intro begins and ends.
-                pub async fn intro(&self) -> Result<Option<(f64, f64)>> {
+                /// Get the segments of the episode / movie which can be skipped, like the intro,
+                /// credits, recap and preview. Is [`None`] if Crunchyroll has no skip data for it.
+                pub async fn skip_events(&self) -> Result<Option<SkipEvents>> {
                     let endpoint = format!(
-                        "https://static.crunchyroll.com/datalab-intro-v2/{}.json",
+                        "https://static.crunchyroll.com/skip-events/production/{}.json",
                         self.id
                     );
-                    let raw_result = self.executor.get(endpoint)
-                        .request_raw()
-                        .await?;
-                    let result = String::from_utf8_lossy(raw_result.as_slice());
-                    if result.contains("</Error>") {
-                        Ok(None)
-                    } else {
-                        let video_intro_result: VideoIntroResult = serde_json::from_str(&result)?;
-                        Ok(Some((video_intro_result.start_time, video_intro_result.end_time)))
+                    let resp = self.executor.get(endpoint).send().await?;
+                    // the endpoint returns 403 instead of 404 if no data is available for the id
+                    if matches!(resp.status(), http::StatusCode::FORBIDDEN | http::StatusCode::NOT_FOUND) {
+                        return Ok(None)
                     }
+                    Ok(Some(serde_json::from_slice(&resp.error_for_status()?.bytes().await?)?))
+                }
+
+                /// Get time _in seconds_ when the episode / movie intro begins and ends.
+                #[deprecated(since = "0.2.6", note = "use `skip_events` instead")]
+                pub async fn intro(&self) -> Result<Option<(f64, f64)>> {
+                    Ok(self
+                        .skip_events()
+                        .await?
+                        .and_then(|skip_events| skip_events.intro)
+                        .map(|intro| (
+                            intro.start.num_milliseconds() as f64 / 1000.0,
+                            intro.end.num_milliseconds() as f64 / 1000.0,
+                        )))
                 }
 
                 /// Return the previous episode / movie. Is [`None`] if the current media is the
@@ -344,13 +435,7 @@ macro_rules! impl_media_video {
                 /// integration so if you update the playhead and have Crunchyroll connected to
                 /// Discord, this episode / movie will be shown as your Discord status.
                 pub async fn set_playhead(&self, position: u32) -> Result<()> {
-                    let endpoint = format!("https://www.crunchyroll.com/content/v2/{}/playheads", self.executor.details.account_id.clone()?);
-                    self.executor.post(endpoint)
-                        .apply_locale_query()
-                        .json(&serde_json::json!({"content_id": &self.id, "playhead": position}))
-                        .request::<$crate::EmptyJsonProxy>()
-                        .await?;
-                    Ok(())
+                    $crate::media::anime::playheads::set_playhead(&self.executor, &self.id, position).await
                 }
             }
         )*
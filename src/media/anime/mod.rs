@@ -2,9 +2,11 @@ mod episode;
 mod r#impl;
 mod movie;
 mod movie_listing;
+pub(crate) mod playheads;
 mod season;
 mod series;
 pub(crate) mod util;
+mod version_matrix;
 
 pub use episode::*;
 pub use movie::*;
@@ -12,3 +14,4 @@ pub use movie_listing::*;
 pub use r#impl::*;
 pub use season::*;
 pub use series::*;
+pub use version_matrix::*;
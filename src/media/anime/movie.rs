@@ -9,6 +9,7 @@ use std::sync::Arc;
 /// Metadata for a movie.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(remote = "Self")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -34,6 +35,10 @@ pub struct Movie {
 
     #[serde(alias = "duration_ms")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub duration: Duration,
 
@@ -96,4 +101,8 @@ impl Media for Movie {
         .await?
         .remove(0))
     }
+
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/cms/objects"
+    }
 }
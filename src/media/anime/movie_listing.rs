@@ -9,6 +9,7 @@ use std::sync::Arc;
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub(crate) struct MovieListingVersion {
@@ -25,6 +26,7 @@ pub(crate) struct MovieListingVersion {
 /// Metadata for a movie listing.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(remote = "Self")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -144,4 +146,8 @@ impl Media for MovieListing {
         .await?
         .remove(0))
     }
+
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/cms/objects"
+    }
 }
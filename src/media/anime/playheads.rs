@@ -0,0 +1,200 @@
+use crate::common::V2BulkResult;
+use crate::media::util::{MAX_CONCURRENT_REQUESTS, MAX_IDS_PER_REQUEST};
+use crate::media::PlayheadInformation;
+use crate::{
+    Crunchyroll, EmptyJsonProxy, Episode, Executor, Movie, MovieListing, Result, Season, Series,
+};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Request the playheads of all `ids`. The ids are split into chunks of [`MAX_IDS_PER_REQUEST`]
+/// of which up to [`MAX_CONCURRENT_REQUESTS`] are requested concurrently.
+pub(crate) async fn request_playheads(
+    executor: &Arc<Executor>,
+    ids: &[String],
+) -> Result<HashMap<String, PlayheadInformation>> {
+    let endpoint = format!(
+        "https://www.crunchyroll.com/content/v2/{}/playheads",
+        executor.details.account_id.clone()?
+    );
+
+    let chunks = ids.chunks(MAX_IDS_PER_REQUEST).map(|chunk| {
+        executor
+            .get(&endpoint)
+            .query(&[("content_ids", chunk.join(","))])
+            .apply_locale_query()
+            .request::<V2BulkResult<PlayheadInformation>>()
+    });
+
+    Ok(stream::iter(chunks)
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+        .try_collect::<Vec<V2BulkResult<PlayheadInformation>>>()
+        .await?
+        .into_iter()
+        .flat_map(|result| result.data)
+        .map(|playhead| (playhead.content_id.clone(), playhead))
+        .collect())
+}
+
+pub(crate) async fn set_playhead(executor: &Arc<Executor>, id: &str, position: u32) -> Result<()> {
+    let endpoint = format!(
+        "https://www.crunchyroll.com/content/v2/{}/playheads",
+        executor.details.account_id.clone()?
+    );
+    executor
+        .post(endpoint)
+        .apply_locale_query()
+        .json(&serde_json::json!({"content_id": id, "playhead": position}))
+        .request::<EmptyJsonProxy>()
+        .await?;
+    Ok(())
+}
+
+/// Remove the episode / movie with id `id` from the watch history, which also resets its playhead.
+pub(crate) async fn remove_from_watch_history(executor: &Arc<Executor>, id: &str) -> Result<()> {
+    let endpoint = format!(
+        "https://www.crunchyroll.com/content/v2/{}/watch-history/{}",
+        executor.details.account_id.clone()?,
+        id
+    );
+    executor
+        .delete(endpoint)
+        .apply_locale_query()
+        .request::<EmptyJsonProxy>()
+        .await?;
+    Ok(())
+}
+
+/// Mark all `episodes` as watched / unwatched with up to [`MAX_CONCURRENT_REQUESTS`] concurrent
+/// requests and return the result for every episode id, in the order of `episodes`.
+async fn mark_episodes(
+    executor: &Arc<Executor>,
+    episodes: Vec<Episode>,
+    watched: bool,
+) -> Vec<(String, Result<()>)> {
+    stream::iter(episodes.into_iter().map(|episode| async move {
+        let result = if watched {
+            set_playhead(executor, &episode.id, episode.duration.num_seconds() as u32).await
+        } else {
+            remove_from_watch_history(executor, &episode.id).await
+        };
+        (episode.id, result)
+    }))
+    .buffered(MAX_CONCURRENT_REQUESTS)
+    .collect()
+    .await
+}
+
+impl Crunchyroll {
+    /// Return the playheads of multiple episodes / movies at once. The ids are requested in as few
+    /// requests as possible of which a limited number are running concurrently. Ids of episodes / movies which were
+    /// never watched are not contained in the returned map.
+    pub async fn playheads(
+        &self,
+        ids: &[impl AsRef<str>],
+    ) -> Result<HashMap<String, PlayheadInformation>> {
+        let ids: Vec<String> = ids.iter().map(|id| id.as_ref().to_string()).collect();
+        request_playheads(&self.executor, &ids).await
+    }
+
+    /// Set the playheads of multiple episodes / movies at once. Every element of `playheads` is the
+    /// id of an episode / movie and the position (in seconds) its playhead should be set to. Every
+    /// playhead is updated with its own request, a limited number of them are running
+    /// concurrently. Failed updates do not abort the other ones, the returned [`Vec`] contains the
+    /// result of every update in the same order as `playheads`.
+    pub async fn set_playheads(&self, playheads: &[(impl AsRef<str>, u32)]) -> Vec<Result<()>> {
+        stream::iter(
+            playheads
+                .iter()
+                .map(|(id, position)| set_playhead(&self.executor, id.as_ref(), *position)),
+        )
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await
+    }
+}
+
+macro_rules! impl_mark_watched {
+    ($($media_video:ident)*) => {
+        $(
+            impl $media_video {
+                /// Mark this episode / movie as watched by setting its playhead to its full
+                /// duration.
+                pub async fn mark_watched(&self) -> Result<()> {
+                    set_playhead(&self.executor, &self.id, self.duration.num_seconds() as u32).await
+                }
+
+                /// Mark this episode / movie as unwatched by removing it from the watch history.
+                pub async fn mark_unwatched(&self) -> Result<()> {
+                    remove_from_watch_history(&self.executor, &self.id).await
+                }
+            }
+        )*
+    }
+}
+
+impl_mark_watched! {
+    Episode Movie
+}
+
+impl Season {
+    /// Mark all episodes of this season as watched. See [`Episode::mark_watched`]. A limited number
+    /// of episodes are updated concurrently. Episodes which fail to update do not abort the other
+    /// ones, check the returned result for every episode id (in the order of the episodes) to get
+    /// the failed ones.
+    pub async fn mark_watched(&self) -> Result<Vec<(String, Result<()>)>> {
+        Ok(mark_episodes(&self.executor, self.episodes().await?, true).await)
+    }
+
+    /// Mark all episodes of this season as unwatched. See [`Episode::mark_unwatched`]. Like
+    /// [`Season::mark_watched`], the result for every episode id is returned.
+    pub async fn mark_unwatched(&self) -> Result<Vec<(String, Result<()>)>> {
+        Ok(mark_episodes(&self.executor, self.episodes().await?, false).await)
+    }
+
+    /// Return the playheads of all episodes of this season. Episodes which were never watched are
+    /// not contained in the returned map.
+    pub async fn playheads(&self) -> Result<HashMap<String, PlayheadInformation>> {
+        let ids: Vec<String> = self.episodes().await?.into_iter().map(|e| e.id).collect();
+        request_playheads(&self.executor, &ids).await
+    }
+}
+
+impl MovieListing {
+    /// Return the playheads of all movies of this movie listing. Movies which were never watched
+    /// are not contained in the returned map.
+    pub async fn playheads(&self) -> Result<HashMap<String, PlayheadInformation>> {
+        let ids: Vec<String> = self.movies().await?.into_iter().map(|m| m.id).collect();
+        request_playheads(&self.executor, &ids).await
+    }
+}
+
+impl Series {
+    /// Mark all episodes of all seasons of this series as watched. See [`Episode::mark_watched`].
+    /// A limited number of episodes are updated concurrently. Episodes which fail to update do not
+    /// abort the other ones, check the returned result for every episode id (in the order of the
+    /// seasons and episodes) to get the failed ones. An error is only returned if the seasons or
+    /// episodes of the series can't be requested.
+    pub async fn mark_watched(&self) -> Result<Vec<(String, Result<()>)>> {
+        Ok(mark_episodes(&self.executor, self.all_episodes().await?, true).await)
+    }
+
+    /// Mark all episodes of all seasons of this series as unwatched. See
+    /// [`Episode::mark_unwatched`]. Like [`Series::mark_watched`], the result for every episode id
+    /// is returned.
+    pub async fn mark_unwatched(&self) -> Result<Vec<(String, Result<()>)>> {
+        Ok(mark_episodes(&self.executor, self.all_episodes().await?, false).await)
+    }
+
+    async fn all_episodes(&self) -> Result<Vec<Episode>> {
+        let seasons = self.seasons().await?;
+        Ok(stream::iter(seasons.iter().map(|s| s.episodes()))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect::<Vec<Vec<Episode>>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
@@ -2,12 +2,13 @@ use crate::crunchyroll::Executor;
 use crate::media::anime::util::{parse_locale_from_slug_title, real_dedup_vec};
 use crate::media::util::request_media;
 use crate::media::Media;
-use crate::{Crunchyroll, Episode, Locale, Result};
+use crate::{Crunchyroll, Episode, Locale, Result, Series};
 use serde::Deserialize;
 use std::sync::Arc;
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub(crate) struct SeasonVersion {
@@ -24,6 +25,7 @@ pub(crate) struct SeasonVersion {
 /// Metadata for a season.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(remote = "Self")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -89,7 +91,7 @@ pub struct Season {
 
 impl Season {
     /// Returns the series the season belongs to.
-    pub async fn series(&self) -> Result<Season> {
+    pub async fn series(&self) -> Result<Series> {
         let endpoint = format!(
             "https://www.crunchyroll.com/content/v2/cms/series/{}",
             self.series_id
@@ -123,6 +125,10 @@ impl Media for Season {
         .remove(0))
     }
 
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/cms/objects"
+    }
+
     async fn __apply_fixes(&mut self) {
         if let Some(audio_locale) = &self.audio_locale {
             self.audio_locales.push(audio_locale.clone());
@@ -10,6 +10,7 @@ use std::sync::Arc;
 /// Metadata for a series.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(remote = "Self")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -114,6 +115,10 @@ impl Media for Series {
         .remove(0))
     }
 
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/cms/objects"
+    }
+
     #[cfg(feature = "experimental-stabilizations")]
     async fn __apply_experimental_stabilizations(&mut self) {
         if self.executor.fixes.locale_name_parsing {
@@ -0,0 +1,230 @@
+use crate::error::CrunchyrollError;
+use crate::media::anime::season::SeasonVersion;
+use crate::media::util::{request_media, MAX_CONCURRENT_REQUESTS};
+use crate::{Crunchyroll, Episode, Locale, Result, Season, Series};
+use futures_util::future::join;
+use futures_util::{stream, StreamExt};
+use std::collections::HashMap;
+
+/// A single audio version of a season in a [`VersionMatrix`].
+#[derive(Clone, Debug, Default)]
+pub struct SeasonVersionInfo {
+    /// Id of the season which has this audio version.
+    pub id: String,
+    pub audio_locale: Locale,
+    /// Empty if the season of this version could not be requested.
+    pub subtitle_locales: Vec<Locale>,
+    /// If this version is the original (in most cases the japanese) version of the season.
+    pub original: bool,
+
+    /// Error which occurred while requesting the season or its episodes. If set, the episodes of
+    /// this version are missing in [`SeasonVersions::episodes`].
+    pub error: Option<CrunchyrollError>,
+}
+
+impl SeasonVersionInfo {
+    /// If the season and all episodes of this version could be requested.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A single audio version of an episode in a [`VersionMatrix`].
+#[derive(Clone, Debug, Default)]
+pub struct EpisodeVersionInfo {
+    /// Id of the episode which has this audio version.
+    pub id: String,
+    pub audio_locale: Locale,
+    pub subtitle_locales: Vec<Locale>,
+    pub is_premium_only: bool,
+    /// If this version is the original (in most cases the japanese) version of the episode.
+    pub original: bool,
+}
+
+/// All audio versions of an episode.
+#[derive(Clone, Debug, Default)]
+pub struct EpisodeVersions {
+    pub episode_number: u32,
+    /// See [`Episode::sequence_number`].
+    pub sequence_number: f32,
+
+    pub versions: Vec<EpisodeVersionInfo>,
+}
+
+/// All audio versions of a season and its episodes.
+#[derive(Clone, Debug, Default)]
+pub struct SeasonVersions {
+    pub season_number: u32,
+
+    pub versions: Vec<SeasonVersionInfo>,
+    pub episodes: Vec<EpisodeVersions>,
+}
+
+impl SeasonVersions {
+    /// If all versions of this season could be requested. See [`SeasonVersionInfo::error`].
+    pub fn is_complete(&self) -> bool {
+        self.versions.iter().all(|v| v.is_complete())
+    }
+}
+
+/// Overview which audio and subtitle locales are available for every season and episode of a
+/// series. Get it via [`Series::version_matrix`].
+#[derive(Clone, Debug, Default)]
+pub struct VersionMatrix {
+    pub series_id: String,
+
+    pub seasons: Vec<SeasonVersions>,
+}
+
+impl VersionMatrix {
+    /// If all seasons and episodes of the series could be requested.
+    pub fn is_complete(&self) -> bool {
+        self.seasons.iter().all(|s| s.is_complete())
+    }
+}
+
+impl Series {
+    /// Return which audio and subtitle locales are available for every season and episode of this
+    /// series. Seasons which are dubbed versions of the same content are grouped together in one
+    /// [`SeasonVersions`]. The seasons and episodes are requested concurrently, with a limited
+    /// number of episode requests running at once. Versions which can't be requested (e.g.
+    /// because they are not available in your region) are only listed with the information their
+    /// original season provides and have [`SeasonVersionInfo::error`] set, use
+    /// [`VersionMatrix::is_complete`] to check if the matrix is complete.
+    pub async fn version_matrix(&self) -> Result<VersionMatrix> {
+        let crunchyroll = Crunchyroll {
+            executor: self.executor.clone(),
+        };
+
+        // group all seasons which are versions of each other
+        let mut groups: Vec<Vec<SeasonVersion>> = vec![];
+        for season in self.seasons().await? {
+            let versions = season.versions.clone().unwrap_or_else(|| {
+                vec![SeasonVersion {
+                    id: season.id.clone(),
+                    audio_locale: season.audio_locales.first().cloned().unwrap_or_default(),
+                    original: true,
+                    variant: "".to_string(),
+                }]
+            });
+
+            if let Some(group) = groups
+                .iter_mut()
+                .find(|g| g.iter().any(|v| versions.iter().any(|vv| vv.id == v.id)))
+            {
+                for version in versions {
+                    if !group.iter().any(|v| v.id == version.id) {
+                        group.push(version)
+                    }
+                }
+            } else {
+                groups.push(versions)
+            }
+        }
+        for group in groups.iter_mut() {
+            // the original version should always be at first
+            group.sort_by_key(|v| !v.original)
+        }
+
+        let season_ids: Vec<String> = groups.iter().flatten().map(|v| v.id.clone()).collect();
+        let episode_requests = season_ids.iter().map(|id| {
+            request_media::<Episode>(
+                self.executor.clone(),
+                format!("https://www.crunchyroll.com/content/v2/cms/seasons/{id}/episodes"),
+            )
+        });
+        let (seasons, episodes) = join(
+            crunchyroll.media_from_ids::<Season>(&season_ids),
+            stream::iter(episode_requests)
+                .buffered(MAX_CONCURRENT_REQUESTS)
+                .collect::<Vec<_>>(),
+        )
+        .await;
+        let seasons: HashMap<String, Result<Season>> =
+            season_ids.iter().cloned().zip(seasons).collect();
+        let mut episodes: HashMap<String, Result<Vec<Episode>>> =
+            season_ids.into_iter().zip(episodes).collect();
+
+        let mut matrix = VersionMatrix {
+            series_id: self.id.clone(),
+            seasons: vec![],
+        };
+        for group in groups {
+            let mut season_versions = SeasonVersions {
+                season_number: group
+                    .iter()
+                    .find_map(|v| match seasons.get(&v.id) {
+                        Some(Ok(season)) => Some(season.season_number),
+                        _ => None,
+                    })
+                    .unwrap_or_default(),
+                ..Default::default()
+            };
+            // maps the id of the original episode to the index of its row in
+            // `season_versions.episodes`
+            let mut episode_rows: HashMap<String, usize> = HashMap::new();
+
+            for version in group {
+                let mut error = None;
+                let version_episodes = match episodes.remove(&version.id) {
+                    Some(Ok(version_episodes)) => version_episodes,
+                    Some(Err(err)) => {
+                        error = Some(err);
+                        vec![]
+                    }
+                    None => vec![],
+                };
+                let subtitle_locales = match seasons.get(&version.id) {
+                    Some(Ok(season)) => season.subtitle_locales.clone(),
+                    Some(Err(err)) => {
+                        error = error.or_else(|| Some(err.clone()));
+                        vec![]
+                    }
+                    None => vec![],
+                };
+
+                for episode in version_episodes {
+                    let episode_versions = episode.versions.clone().unwrap_or_default();
+                    let key = episode_versions
+                        .iter()
+                        .find(|v| v.original)
+                        .map(|v| v.id.clone())
+                        .or_else(|| episode_versions.iter().map(|v| v.id.clone()).min())
+                        .unwrap_or_else(|| episode.id.clone());
+                    let row = *episode_rows.entry(key).or_insert_with(|| {
+                        season_versions.episodes.push(EpisodeVersions {
+                            episode_number: episode.episode_number,
+                            sequence_number: episode.sequence_number,
+                            versions: vec![],
+                        });
+                        season_versions.episodes.len() - 1
+                    });
+
+                    season_versions.episodes[row]
+                        .versions
+                        .push(EpisodeVersionInfo {
+                            original: !episode_versions
+                                .iter()
+                                .any(|v| v.id == episode.id && !v.original),
+                            id: episode.id,
+                            audio_locale: episode.audio_locale,
+                            subtitle_locales: episode.subtitle_locales,
+                            is_premium_only: episode.is_premium_only,
+                        })
+                }
+
+                season_versions.versions.push(SeasonVersionInfo {
+                    id: version.id,
+                    audio_locale: version.audio_locale,
+                    subtitle_locales,
+                    original: version.original,
+                    error,
+                })
+            }
+
+            matrix.seasons.push(season_versions)
+        }
+
+        Ok(matrix)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    Concert, Crunchyroll, Episode, Executor, Movie, MovieListing, MusicVideo, Request, Season,
+    Series,
+};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+/// Executor which is shared by all detached media. It has no session and every request which is
+/// made with it fails with a [`crate::error::CrunchyrollError::Input`] error.
+fn detached_executor() -> Arc<Executor> {
+    static EXECUTOR: OnceLock<Arc<Executor>> = OnceLock::new();
+    EXECUTOR
+        .get_or_init(|| {
+            Arc::new(Executor {
+                detached: true,
+                ..Default::default()
+            })
+        })
+        .clone()
+}
+
+/// Snapshot of a media which isn't bound to any session. Holding it doesn't keep the session it was
+/// requested with alive, so it can be stored, compared and sent to other threads (or, with the
+/// `serde` feature, to other processes) freely. All fields of the media are accessible through
+/// [`Deref`]. Use [`Detached::attach`] to bind it to a session again to use functions which
+/// request the api, like [`Episode::streams`]. Calling them on the detached media directly returns
+/// an [`crate::error::CrunchyrollError::Input`] error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Detached<M>(M);
+
+impl<M> Deref for Detached<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M: Request> Detached<M> {
+    /// Bind the media to the session of `crunchyroll`.
+    pub async fn attach(self, crunchyroll: &Crunchyroll) -> M {
+        let mut media = self.0;
+        media.__set_executor(crunchyroll.executor.clone()).await;
+        media
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<M: serde::Serialize> serde::Serialize for Detached<M> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+macro_rules! impl_detach {
+    ($($media:ident $(($($nested:ident)*))?)*) => {
+        $(
+            impl $media {
+                /// Detach this media from its session. See [`Detached`] for more information.
+                pub fn detach(mut self) -> Detached<Self> {
+                    self.executor = detached_executor();
+                    $($(self.$nested.executor = detached_executor();)*)?
+                    Detached(self)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> serde::Deserialize<'de> for Detached<$media> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    Ok(<$media as serde::Deserialize>::deserialize(deserializer)?.detach())
+                }
+            }
+        )*
+    }
+}
+
+impl_detach! {
+    Series Season Episode MovieListing Movie MusicVideo(artist) Concert(artist)
+}
@@ -0,0 +1,243 @@
+use crate::media::Artist;
+use crate::{
+    Concert, Episode, MediaCollection, Movie, MovieListing, MusicVideo, Result, Season, Series,
+};
+use futures_util::future::try_join;
+use std::convert::Infallible;
+
+/// Uniform navigation through the media tree. A [`Series`] has [`Season`]s which have
+/// [`Episode`]s, a [`MovieListing`] has [`Movie`]s and an [`Artist`] has [`MusicVideo`]s and
+/// [`Concert`]s. Media which has no parent or no children uses [`Infallible`] as associated type,
+/// its [`MediaHierarchy::parent`] always returns [`None`] and [`MediaHierarchy::children`] always
+/// an empty [`Vec`].
+///
+/// [`MediaCollection`] implements this trait with [`MediaCollection`] as every associated type, use
+/// it if you want to walk through any media tree without knowing its types.
+#[async_trait::async_trait]
+pub trait MediaHierarchy {
+    type Parent: Send;
+    type Child: Send;
+    type Root: Send;
+
+    /// Return the media this media is part of. [`None`] if the media is at the top of its tree.
+    async fn parent(&self) -> Result<Option<Self::Parent>>;
+
+    /// Return all media which are part of this media.
+    async fn children(&self) -> Result<Vec<Self::Child>>;
+
+    /// Return the media at the top of the tree this media is part of. This is the media itself if
+    /// it has no parent.
+    async fn root(&self) -> Result<Self::Root>;
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for Series {
+    type Parent = Infallible;
+    type Child = Season;
+    type Root = Series;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(None)
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        self.seasons().await
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        Ok(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for Season {
+    type Parent = Series;
+    type Child = Episode;
+    type Root = Series;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(Some(self.series().await?))
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        self.episodes().await
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        self.series().await
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for Episode {
+    type Parent = Season;
+    type Child = Infallible;
+    type Root = Series;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(Some(self.season().await?))
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        Ok(vec![])
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        self.series().await
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for MovieListing {
+    type Parent = Infallible;
+    type Child = Movie;
+    type Root = MovieListing;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(None)
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        self.movies().await
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        Ok(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for Movie {
+    type Parent = MovieListing;
+    type Child = Infallible;
+    type Root = MovieListing;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(Some(self.movie_listing().await?))
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        Ok(vec![])
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        self.movie_listing().await
+    }
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for Artist {
+    type Parent = Infallible;
+    /// Either [`MediaCollection::MusicVideo`] or [`MediaCollection::Concert`].
+    type Child = MediaCollection;
+    type Root = Artist;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(None)
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        let (music_videos, concerts) = try_join(self.music_videos(), self.concerts()).await?;
+        Ok(music_videos
+            .into_iter()
+            .map(MediaCollection::from)
+            .chain(concerts.into_iter().map(MediaCollection::from))
+            .collect())
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        Ok(self.clone())
+    }
+}
+
+macro_rules! impl_music_hierarchy {
+    ($($media:ident)*) => {
+        $(
+            #[async_trait::async_trait]
+            impl MediaHierarchy for $media {
+                type Parent = Artist;
+                type Child = Infallible;
+                type Root = Artist;
+
+                async fn parent(&self) -> Result<Option<Self::Parent>> {
+                    Ok(Some(self.artist.artist().await?))
+                }
+
+                async fn children(&self) -> Result<Vec<Self::Child>> {
+                    Ok(vec![])
+                }
+
+                async fn root(&self) -> Result<Self::Root> {
+                    self.artist.artist().await
+                }
+            }
+        )*
+    }
+}
+
+impl_music_hierarchy! {
+    MusicVideo Concert
+}
+
+#[async_trait::async_trait]
+impl MediaHierarchy for MediaCollection {
+    type Parent = MediaCollection;
+    type Child = MediaCollection;
+    type Root = MediaCollection;
+
+    async fn parent(&self) -> Result<Option<Self::Parent>> {
+        Ok(match self {
+            MediaCollection::Season(season) => Some(season.series().await?.into()),
+            MediaCollection::Episode(episode) => Some(episode.season().await?.into()),
+            MediaCollection::Movie(movie) => Some(movie.movie_listing().await?.into()),
+            MediaCollection::MusicVideo(music_video) => {
+                Some(music_video.artist.artist().await?.into())
+            }
+            MediaCollection::Concert(concert) => Some(concert.artist.artist().await?.into()),
+            MediaCollection::Series(_)
+            | MediaCollection::MovieListing(_)
+            | MediaCollection::Artist(_) => None,
+        })
+    }
+
+    async fn children(&self) -> Result<Vec<Self::Child>> {
+        Ok(match self {
+            MediaCollection::Series(series) => series
+                .seasons()
+                .await?
+                .into_iter()
+                .map(|s| s.into())
+                .collect(),
+            MediaCollection::Season(season) => season
+                .episodes()
+                .await?
+                .into_iter()
+                .map(|e| e.into())
+                .collect(),
+            MediaCollection::MovieListing(movie_listing) => movie_listing
+                .movies()
+                .await?
+                .into_iter()
+                .map(|m| m.into())
+                .collect(),
+            MediaCollection::Artist(artist) => artist.children().await?,
+            MediaCollection::Episode(_)
+            | MediaCollection::Movie(_)
+            | MediaCollection::MusicVideo(_)
+            | MediaCollection::Concert(_) => vec![],
+        })
+    }
+
+    async fn root(&self) -> Result<Self::Root> {
+        Ok(match self {
+            MediaCollection::Season(season) => season.series().await?.into(),
+            MediaCollection::Episode(episode) => episode.series().await?.into(),
+            MediaCollection::Movie(movie) => movie.movie_listing().await?.into(),
+            MediaCollection::MusicVideo(music_video) => music_video.artist.artist().await?.into(),
+            MediaCollection::Concert(concert) => concert.artist.artist().await?.into(),
+            MediaCollection::Series(_)
+            | MediaCollection::MovieListing(_)
+            | MediaCollection::Artist(_) => self.clone(),
+        })
+    }
+}
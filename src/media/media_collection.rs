@@ -1,7 +1,8 @@
 use crate::common::Request;
 use crate::crunchyroll::Executor;
 use crate::error::CrunchyrollError;
-use crate::media::Media;
+use crate::media::util::{order_media_by_ids, request_media_by_ids};
+use crate::media::{Artist, Media};
 use crate::{
     Concert, Crunchyroll, Episode, Movie, MovieListing, MusicVideo, Result, Season, Series,
 };
@@ -22,6 +23,7 @@ pub enum MediaCollection {
     Movie(Movie),
     MusicVideo(MusicVideo),
     Concert(Concert),
+    Artist(Artist),
 }
 
 impl MediaCollection {
@@ -49,6 +51,62 @@ impl MediaCollection {
             ))
         }
     }
+
+    /// Request multiple media at once. Anime ids are requested at first, all ids which weren't
+    /// found are then requested as music videos and after that as concerts. The returned [`Vec`]
+    /// has the same order as `ids`, every id which could not be found results in an error at its
+    /// position.
+    pub async fn from_ids<S: AsRef<str>>(
+        crunchyroll: &Crunchyroll,
+        ids: &[S],
+    ) -> Vec<Result<MediaCollection>> {
+        let ids: Vec<String> = ids.iter().map(|id| id.as_ref().to_string()).collect();
+
+        let mut media = request_media_by_ids(
+            crunchyroll.executor.clone(),
+            "https://www.crunchyroll.com/content/v2/cms/objects",
+            &ids,
+        )
+        .await;
+        for endpoint in [
+            "https://www.crunchyroll.com/content/v2/music/music_videos",
+            "https://www.crunchyroll.com/content/v2/music/concerts",
+        ] {
+            let missing: Vec<String> = ids
+                .iter()
+                .filter(|id| !matches!(media.get(*id), Some(Ok(_))))
+                .cloned()
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+
+            for (id, result) in
+                request_media_by_ids(crunchyroll.executor.clone(), endpoint, &missing).await
+            {
+                // keep the first error if the media couldn't be found on any endpoint
+                if result.is_ok() || !media.contains_key(&id) {
+                    media.insert(id, result);
+                }
+            }
+        }
+
+        order_media_by_ids(&ids, media)
+    }
+
+    /// Return the id of the underlying media.
+    pub fn id(&self) -> &str {
+        match self {
+            MediaCollection::Series(series) => &series.id,
+            MediaCollection::Season(season) => &season.id,
+            MediaCollection::Episode(episode) => &episode.id,
+            MediaCollection::MovieListing(movie_listing) => &movie_listing.id,
+            MediaCollection::Movie(movie) => &movie.id,
+            MediaCollection::MusicVideo(music_video) => &music_video.id,
+            MediaCollection::Concert(concert) => &concert.id,
+            MediaCollection::Artist(artist) => &artist.id,
+        }
+    }
 }
 
 impl Default for MediaCollection {
@@ -93,12 +151,17 @@ impl<'de> Deserialize<'de> for MediaCollection {
             Ok(MediaCollection::Movie(
                 serde_json::from_value(Value::from(as_map)).map_err(err_conv)?,
             ))
+        } else if as_map.contains_key("concerts") && as_map.contains_key("videos") {
+            Ok(MediaCollection::Artist(
+                serde_json::from_value(Value::from(as_map)).map_err(err_conv)?,
+            ))
         } else if as_map.contains_key("animeIds") {
             Ok(MediaCollection::MusicVideo(
                 serde_json::from_value(Value::from(as_map)).map_err(err_conv)?,
             ))
-        // music video contains this field too so music video must be checked before this condition
-        } else if as_map.contains_key("availability") {
+        // music video contains this field too so music video must be checked before this condition.
+        // `availabilityStarts` is present if the concert was serialized by this crate
+        } else if as_map.contains_key("availability") || as_map.contains_key("availabilityStarts") {
             Ok(MediaCollection::Concert(
                 serde_json::from_value(Value::from(as_map)).map_err(err_conv)?,
             ))
@@ -110,6 +173,25 @@ impl<'de> Deserialize<'de> for MediaCollection {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for MediaCollection {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            MediaCollection::Series(series) => series.serialize(serializer),
+            MediaCollection::Season(season) => season.serialize(serializer),
+            MediaCollection::Episode(episode) => episode.serialize(serializer),
+            MediaCollection::MovieListing(movie_listing) => movie_listing.serialize(serializer),
+            MediaCollection::Movie(movie) => movie.serialize(serializer),
+            MediaCollection::MusicVideo(music_video) => music_video.serialize(serializer),
+            MediaCollection::Concert(concert) => concert.serialize(serializer),
+            MediaCollection::Artist(artist) => artist.serialize(serializer),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl Request for MediaCollection {
     async fn __set_executor(&mut self, executor: Arc<Executor>) {
@@ -123,6 +205,7 @@ impl Request for MediaCollection {
             MediaCollection::Movie(movie) => movie.__set_executor(executor).await,
             MediaCollection::MusicVideo(music_video) => music_video.__set_executor(executor).await,
             MediaCollection::Concert(concert) => concert.__set_executor(executor).await,
+            MediaCollection::Artist(artist) => artist.__set_executor(executor).await,
         }
     }
 }
@@ -135,10 +218,24 @@ macro_rules! impl_media_collection {
                     MediaCollection::$media(value)
                 }
             }
+
+            impl TryFrom<MediaCollection> for $media {
+                type Error = CrunchyrollError;
+
+                fn try_from(value: MediaCollection) -> Result<Self> {
+                    if let MediaCollection::$media(media) = value {
+                        Ok(media)
+                    } else {
+                        Err(CrunchyrollError::Input(
+                            format!("media with id '{}' is no {}", value.id(), stringify!($media)).into(),
+                        ))
+                    }
+                }
+            }
         )*
     }
 }
 
 impl_media_collection! {
-    Series Season Episode MovieListing Movie MusicVideo Concert
+    Series Season Episode MovieListing Movie MusicVideo Concert Artist
 }
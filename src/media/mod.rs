@@ -1,13 +1,17 @@
 mod anime;
+mod detached;
+mod hierarchy;
 mod media_collection;
 mod music;
 mod shared;
 mod stream;
 #[cfg(any(feature = "hls-stream", feature = "dash-stream"))]
 mod streaming;
-mod util;
+pub(crate) mod util;
 
 pub use anime::*;
+pub use detached::*;
+pub use hierarchy::*;
 pub use media_collection::*;
 pub use music::*;
 pub use shared::*;
@@ -15,12 +19,16 @@ pub use stream::*;
 #[cfg(any(feature = "hls-stream", feature = "dash-stream"))]
 pub use streaming::*;
 
+use crate::error::CrunchyrollError;
+use crate::media::util::{order_media_by_ids, request_media_by_ids};
 use crate::{Crunchyroll, Result};
 
 crate::enum_values! {
     pub enum MediaType {
         Series = "series"
         Movie = "movie_listing"
+        Episode = "episode"
+        Music = "music"
     }
 }
 
@@ -30,6 +38,13 @@ pub trait Media {
     where
         Self: Sized;
 
+    /// Endpoint which accepts multiple, comma separated ids of this media type. Used by
+    /// [`Crunchyroll::media_from_ids`].
+    #[doc(hidden)]
+    fn __batch_endpoint() -> &'static str
+    where
+        Self: Sized;
+
     #[doc(hidden)]
     async fn __apply_fixes(&mut self) {}
 
@@ -43,7 +58,31 @@ impl Crunchyroll {
         M::from_id(self, id).await
     }
 
+    /// Request multiple media of the same type at once. The ids are requested in as few requests as
+    /// possible which are running concurrently. The returned [`Vec`] has the same order as `ids`,
+    /// every id which could not be found (or isn't of type `M`) results in an error at its position.
+    pub async fn media_from_ids<M>(&self, ids: &[impl AsRef<str>]) -> Vec<Result<M>>
+    where
+        M: Media + TryFrom<MediaCollection, Error = CrunchyrollError>,
+    {
+        let ids: Vec<String> = ids.iter().map(|id| id.as_ref().to_string()).collect();
+        let media = request_media_by_ids(self.executor.clone(), M::__batch_endpoint(), &ids).await;
+
+        order_media_by_ids(&ids, media)
+            .into_iter()
+            .map(|m| m.and_then(M::try_from))
+            .collect()
+    }
+
     pub async fn media_collection_from_id<S: AsRef<str>>(&self, id: S) -> Result<MediaCollection> {
         MediaCollection::from_id(self, id).await
     }
+
+    /// Request multiple media at once. See [`MediaCollection::from_ids`] for more information.
+    pub async fn media_collection_from_ids<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+    ) -> Vec<Result<MediaCollection>> {
+        MediaCollection::from_ids(self, ids).await
+    }
 }
@@ -9,6 +9,7 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct ArtistPreview {
@@ -35,6 +36,7 @@ impl ArtistPreview {
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, Request, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -61,10 +63,18 @@ pub struct Artist {
 
     #[serde(alias = "totalConcertDurationMs")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub total_concert_duration: Duration,
     #[serde(alias = "totalVideoDurationMs")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub total_video_duration: Duration,
 
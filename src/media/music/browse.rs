@@ -0,0 +1,97 @@
+use crate::common::{Pagination, V2BulkResult};
+use crate::media::{Artist, Genre};
+use crate::search::BrowseSortType;
+use crate::{options, Concert, Crunchyroll, MediaCollection, MusicVideo, Request, Result};
+use futures_util::FutureExt;
+use serde::de::DeserializeOwned;
+
+options! {
+    MusicBrowseOptions;
+    /// Specifies the genre of the entries. Use [`Crunchyroll::music_genres`] to get all genres,
+    /// the value must be a [`Genre::id`].
+    genre(String, "genre") = None,
+    /// Specifies the artist of the entries. The value must be a [`Artist::id`].
+    artist(String, "artist") = None,
+    /// Specifies how the entries should be sorted.
+    sort(BrowseSortType, "sort") = Some(BrowseSortType::NewlyAdded)
+}
+
+/// Paginated request to a music endpoint.
+fn music_pagination<T: Default + DeserializeOwned + Request + 'static>(
+    crunchyroll: &Crunchyroll,
+    endpoint: &'static str,
+    query: Option<Vec<(String, String)>>,
+) -> Pagination<T> {
+    Pagination::new(
+        move |options| {
+            async move {
+                let result = options
+                    .executor
+                    .get(endpoint)
+                    .query(&options.query)
+                    .query(&[("n", options.page_size), ("start", options.start)])
+                    .apply_locale_query()
+                    .request::<V2BulkResult<T>>()
+                    .await?;
+                Ok((result.data, result.total))
+            }
+            .boxed()
+        },
+        crunchyroll.executor.clone(),
+        query,
+        None,
+    )
+}
+
+impl Crunchyroll {
+    /// Returns all music genres.
+    pub async fn music_genres(&self) -> Result<Vec<Genre>> {
+        let endpoint = "https://www.crunchyroll.com/content/v2/music/genres";
+        Ok(self
+            .executor
+            .get(endpoint)
+            .apply_locale_query()
+            .request::<V2BulkResult<Genre>>()
+            .await?
+            .data)
+    }
+
+    /// Returns all music artists.
+    pub fn artists(&self) -> Pagination<Artist> {
+        music_pagination(
+            self,
+            "https://www.crunchyroll.com/content/v2/music/artists",
+            None,
+        )
+    }
+
+    /// Browses the crunchyroll music catalog filtered by the specified options and returns all
+    /// found music videos.
+    pub fn browse_music_videos(&self, options: MusicBrowseOptions) -> Pagination<MusicVideo> {
+        music_pagination(
+            self,
+            "https://www.crunchyroll.com/content/v2/music/music_videos",
+            Some(options.into_query()),
+        )
+    }
+
+    /// Browses the crunchyroll music catalog filtered by the specified options and returns all
+    /// found concerts.
+    pub fn browse_concerts(&self, options: MusicBrowseOptions) -> Pagination<Concert> {
+        music_pagination(
+            self,
+            "https://www.crunchyroll.com/content/v2/music/concerts",
+            Some(options.into_query()),
+        )
+    }
+
+    /// Returns the music feed which contains featured and new music videos and concerts. Every
+    /// item is either [`MediaCollection::MusicVideo`] or [`MediaCollection::Concert`].
+    pub fn music_feed(&self) -> Pagination<MediaCollection> {
+        music_pagination(
+            self,
+            "https://www.crunchyroll.com/content/v2/music/feed",
+            None,
+        )
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Deserializer};
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Deserialize, Request, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[request(executor(artist))]
 #[serde(rename_all = "camelCase")]
 #[serde(remote = "Self")]
@@ -45,6 +46,10 @@ pub struct Concert {
 
     #[serde(alias = "durationMs")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub duration: Duration,
     #[default(DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH))]
@@ -90,6 +95,16 @@ impl<'de> Deserialize<'de> for Concert {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Concert {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Concert::serialize(self, serializer)
+    }
+}
+
 #[async_trait::async_trait]
 impl Media for Concert {
     async fn from_id(crunchyroll: &Crunchyroll, id: impl AsRef<str> + Send) -> Result<Self> {
@@ -101,4 +116,8 @@ impl Media for Concert {
             .await?
             .remove(0))
     }
+
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/music/concerts"
+    }
 }
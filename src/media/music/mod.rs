@@ -1,10 +1,12 @@
 mod artist;
+mod browse;
 mod concert;
 mod r#impl;
 mod music_video;
 mod util;
 
 pub use artist::*;
+pub use browse::*;
 pub use concert::*;
 pub use music_video::*;
 
@@ -12,6 +14,7 @@ use crate::Request;
 use serde::Deserialize;
 
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -9,6 +9,7 @@ use serde::{Deserialize, Deserializer};
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Deserialize, Request, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[request(executor(artist))]
 #[serde(rename_all = "camelCase")]
 #[serde(remote = "Self")]
@@ -49,6 +50,10 @@ pub struct MusicVideo {
 
     #[serde(alias = "durationMs")]
     #[serde(deserialize_with = "crate::internal::serde::deserialize_millis_to_duration")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_duration_to_millis")
+    )]
     #[default(Duration::milliseconds(0))]
     pub duration: Duration,
 
@@ -92,6 +97,16 @@ impl<'de> Deserialize<'de> for MusicVideo {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for MusicVideo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        MusicVideo::serialize(self, serializer)
+    }
+}
+
 impl MusicVideo {
     /// Return all related anime with this music video.
     pub async fn related_anime(&self) -> Result<Vec<MediaCollection>> {
@@ -124,4 +139,8 @@ impl Media for MusicVideo {
             .await?
             .remove(0))
     }
+
+    fn __batch_endpoint() -> &'static str {
+        "https://www.crunchyroll.com/content/v2/music/music_videos"
+    }
 }
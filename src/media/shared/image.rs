@@ -3,6 +3,7 @@ use serde::Deserialize;
 use serde_json::{Map, Value};
 
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(try_from = "Map<String, Value>")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -10,6 +11,25 @@ pub struct ThumbnailImages {
     pub thumbnail: Vec<Image>,
 }
 
+impl ThumbnailImages {
+    /// The smallest thumbnail which is at least `width` x `height` big. If no thumbnail is that big,
+    /// the largest is returned. Only thumbnails with the aspect ratio closest to `width` / `height`
+    /// are considered.
+    pub fn best_for(&self, width: u32, height: u32) -> Option<&Image> {
+        best_for(self.thumbnail.iter(), width, height)
+    }
+
+    /// The thumbnail with the most pixels.
+    pub fn largest(&self) -> Option<&Image> {
+        largest(self.thumbnail.iter())
+    }
+
+    /// The largest thumbnail whose aspect ratio (width / height) is closest to `ratio`.
+    pub fn closest_aspect(&self, ratio: f64) -> Option<&Image> {
+        closest_aspect(self.thumbnail.iter(), ratio)
+    }
+}
+
 impl TryFrom<Map<String, Value>> for ThumbnailImages {
     type Error = serde_json::Error;
 
@@ -31,6 +51,7 @@ impl TryFrom<Map<String, Value>> for ThumbnailImages {
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[serde(try_from = "Map<String, Value>")]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -39,6 +60,30 @@ pub struct PosterImages {
     pub poster_wide: Vec<Image>,
 }
 
+impl PosterImages {
+    /// The smallest poster which is at least `width` x `height` big. If no poster is that big, the
+    /// largest is returned. Only posters with the aspect ratio closest to `width` / `height` are
+    /// considered, so this chooses between [`PosterImages::poster_tall`] and
+    /// [`PosterImages::poster_wide`] automatically.
+    pub fn best_for(&self, width: u32, height: u32) -> Option<&Image> {
+        best_for(self.all(), width, height)
+    }
+
+    /// The poster with the most pixels.
+    pub fn largest(&self) -> Option<&Image> {
+        largest(self.all())
+    }
+
+    /// The largest poster whose aspect ratio (width / height) is closest to `ratio`.
+    pub fn closest_aspect(&self, ratio: f64) -> Option<&Image> {
+        closest_aspect(self.all(), ratio)
+    }
+
+    fn all(&self) -> impl Iterator<Item = &Image> + Clone {
+        self.poster_tall.iter().chain(self.poster_wide.iter())
+    }
+}
+
 impl TryFrom<Map<String, Value>> for PosterImages {
     type Error = serde_json::Error;
 
@@ -68,3 +113,42 @@ impl TryFrom<Map<String, Value>> for PosterImages {
         })
     }
 }
+
+/// Images whose aspect ratio differs by less than this are treated as having the same aspect ratio.
+const ASPECT_RATIO_TOLERANCE: f64 = 0.01;
+
+fn largest<'a>(images: impl Iterator<Item = &'a Image>) -> Option<&'a Image> {
+    images.max_by_key(|i| i.width as u64 * i.height as u64)
+}
+
+fn closest_aspect<'a>(
+    images: impl Iterator<Item = &'a Image> + Clone,
+    ratio: f64,
+) -> Option<&'a Image> {
+    let closest = images
+        .clone()
+        .map(|i| (i.aspect_ratio() - ratio).abs())
+        .min_by(|a, b| a.total_cmp(b))?;
+    largest(images.filter(|i| (i.aspect_ratio() - ratio).abs() - closest < ASPECT_RATIO_TOLERANCE))
+}
+
+fn best_for<'a>(
+    images: impl Iterator<Item = &'a Image> + Clone,
+    width: u32,
+    height: u32,
+) -> Option<&'a Image> {
+    let ratio = if height == 0 {
+        0.0
+    } else {
+        width as f64 / height as f64
+    };
+    let target_ratio = closest_aspect(images.clone(), ratio)?.aspect_ratio();
+    let candidates =
+        images.filter(|i| (i.aspect_ratio() - target_ratio).abs() < ASPECT_RATIO_TOLERANCE);
+
+    candidates
+        .clone()
+        .filter(|i| i.width >= width && i.height >= height)
+        .min_by_key(|i| i.width as u64 * i.height as u64)
+        .or_else(|| largest(candidates))
+}
@@ -1,5 +1,6 @@
 use crate::common::V2BulkResult;
 use crate::error::CrunchyrollError;
+use crate::locale::LocalePreference;
 use crate::{Executor, Locale, Request, Result};
 use serde::de::Error;
 use serde::{Deserialize, Deserializer};
@@ -39,8 +40,33 @@ fn deserialize_streams<'de, D: Deserializer<'de>>(
     serde_json::from_value(as_value).map_err(|e| Error::custom(e.to_string()))
 }
 
+/// Counterpart to [`deserialize_streams`].
+#[cfg(feature = "serde")]
+fn serialize_streams<S: serde::Serializer>(
+    variants: &HashMap<Locale, Variants>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::Serialize;
+
+    let mut raw: HashMap<String, HashMap<Locale, Value>> = HashMap::new();
+    for (locale, variants) in variants {
+        let as_value = serde_json::to_value(variants).map_err(serde::ser::Error::custom)?;
+        for (key, data) in as_value.as_object().into_iter().flatten() {
+            if data.is_null() {
+                continue;
+            }
+            raw.entry(key.clone())
+                .or_default()
+                .insert(locale.clone(), data.clone());
+        }
+    }
+
+    raw.serialize(serializer)
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub(crate) struct StreamVersion {
@@ -62,6 +88,7 @@ pub(crate) struct StreamVersion {
 /// A video stream.
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[request(executor(subtitles))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
@@ -85,14 +112,17 @@ pub struct Stream {
     /// are "burned" into the video) and the value all stream variants.
     /// If you want no hardsub at all, use the `Locale::Custom("".into())` map entry.
     #[serde(deserialize_with = "deserialize_streams")]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_streams"))]
     #[cfg_attr(not(feature = "__test_strict"), default(HashMap::new()))]
     pub variants: HashMap<Locale, Variants>,
 
     /// Might be null, for music videos and concerts mostly.
     versions: Option<Vec<StreamVersion>>,
     /// When requesting versions from [`Stream::versions`] this url is required as multiple paths
-    /// exists which can lead to the [`Stream`] struct.
-    #[serde(skip)]
+    /// exists which can lead to the [`Stream`] struct. Not part of the api response but kept when
+    /// (de-)serializing the stream with the `serde` feature.
+    #[cfg_attr(not(feature = "serde"), serde(skip))]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub(crate) version_request_url: String,
 
     #[cfg(feature = "__test_strict")]
@@ -125,6 +155,15 @@ impl Stream {
         Ok(stream)
     }
 
+    fn version_request_url(&self) -> Result<&str> {
+        if self.version_request_url.is_empty() {
+            return Err(CrunchyrollError::Input(
+                "stream was not requested from the api, its versions are unknown".into(),
+            ));
+        }
+        Ok(&self.version_request_url)
+    }
+
     pub fn available_versions(&self) -> Vec<Locale> {
         self.versions
             .clone()
@@ -152,7 +191,7 @@ impl Stream {
         let mut result = vec![];
         for id in version_ids {
             result.push(
-                Stream::from_url(self.executor.clone(), &self.version_request_url, &id).await?,
+                Stream::from_url(self.executor.clone(), self.version_request_url()?, &id).await?,
             );
         }
         Ok(result)
@@ -170,15 +209,59 @@ impl Stream {
         let mut result = vec![];
         for id in version_ids {
             result.push(
-                Stream::from_url(self.executor.clone(), &self.version_request_url, &id).await?,
+                Stream::from_url(self.executor.clone(), self.version_request_url()?, &id).await?,
             )
         }
         Ok(result)
     }
+
+    /// Return the version of this stream whose audio locale matches `preference` best. This is
+    /// the stream itself if its audio locale matches best. [`None`] if no version matches.
+    pub async fn preferred_version(&self, preference: &LocalePreference) -> Result<Option<Stream>> {
+        let mut versions = self.versions.clone().unwrap_or_default();
+        if !versions.iter().any(|v| v.media_id == self.media_id) {
+            versions.push(StreamVersion {
+                media_id: self.media_id.clone(),
+                audio_locale: self.audio_locale.clone(),
+                ..Default::default()
+            })
+        }
+
+        match preference.select(&versions, |v| &v.audio_locale) {
+            Some(version) if version.media_id == self.media_id => Ok(Some(self.clone())),
+            Some(version) => Ok(Some(
+                Stream::from_url(
+                    self.executor.clone(),
+                    self.version_request_url()?,
+                    &version.media_id,
+                )
+                .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the subtitle whose locale matches `preference` best. [`None`] if no subtitle matches.
+    pub fn preferred_subtitle(&self, preference: &LocalePreference) -> Option<&Subtitle> {
+        let mut subtitles: Vec<&Subtitle> = self.subtitles.values().collect();
+        subtitles.sort_by(|a, b| a.locale.cmp(&b.locale));
+        preference.select(subtitles, |s| &s.locale)
+    }
+
+    /// Return the stream variants whose hardsub locale matches `preference` best. [`None`] if no
+    /// hardsub matches, use the `Locale::Custom("".into())` entry of [`Stream::variants`] to get
+    /// the variants without hardsub.
+    pub fn preferred_hardsub(&self, preference: &LocalePreference) -> Option<&Variants> {
+        let mut hardsub_locales: Vec<&Locale> = self.variants.keys().collect();
+        hardsub_locales.sort();
+        let locale = preference.best_match(hardsub_locales)?;
+        self.variants.get(locale)
+    }
 }
 
 /// Subtitle for streams.
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Subtitle {
@@ -201,6 +284,7 @@ impl Subtitle {
 
 /// A [`VideoStream`] variant.
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Variant {
@@ -215,6 +299,7 @@ pub struct Variant {
 /// Stream variants for a [`VideoStream`].
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Variants {
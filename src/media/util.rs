@@ -1,9 +1,21 @@
 use crate::common::{Request, V2BulkResult};
 use crate::crunchyroll::Executor;
-use crate::Result;
+use crate::error::CrunchyrollError;
+use crate::{MediaCollection, Result};
+use futures_util::{stream, StreamExt};
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Maximum number of ids which are requested at once when using a multi id endpoint. Crunchyroll
+/// rejects requests with urls that are too long, so bigger id lists must be split up.
+pub(crate) const MAX_IDS_PER_REQUEST: usize = 50;
+
+/// Maximum number of requests which are running at once when a function has to send many requests,
+/// e.g. one for every episode of a series. Sending all of them at once gets the account rate
+/// limited.
+pub(crate) const MAX_CONCURRENT_REQUESTS: usize = 8;
+
 pub(crate) async fn request_media<T: Default + DeserializeOwned + Request>(
     executor: Arc<Executor>,
     endpoint: String,
@@ -16,3 +28,53 @@ pub(crate) async fn request_media<T: Default + DeserializeOwned + Request>(
         .await?;
     Ok(result.data)
 }
+
+/// Request all media with the given ids from an endpoint which accepts multiple, comma separated
+/// ids. The ids are split into chunks of [`MAX_IDS_PER_REQUEST`] of which up to
+/// [`MAX_CONCURRENT_REQUESTS`] are requested concurrently.
+/// The returned map contains every id which was found or whose chunk request failed. Ids which are
+/// not in the map do not exist (on this endpoint).
+pub(crate) async fn request_media_by_ids(
+    executor: Arc<Executor>,
+    endpoint: &str,
+    ids: &[String],
+) -> HashMap<String, Result<MediaCollection>> {
+    let chunks = ids.chunks(MAX_IDS_PER_REQUEST).map(|chunk| {
+        let executor = executor.clone();
+        async move {
+            let result = request_media::<MediaCollection>(
+                executor,
+                format!("{}/{}", endpoint, chunk.join(",")),
+            )
+            .await;
+            (chunk, result)
+        }
+    });
+
+    let mut results = stream::iter(chunks).buffer_unordered(MAX_CONCURRENT_REQUESTS);
+    let mut media = HashMap::new();
+    while let Some((chunk, result)) = results.next().await {
+        match result {
+            Ok(found) => media.extend(found.into_iter().map(|m| (m.id().to_string(), Ok(m)))),
+            Err(e) => media.extend(chunk.iter().map(|id| (id.clone(), Err(e.clone())))),
+        }
+    }
+    media
+}
+
+/// Bring the result of [`request_media_by_ids`] into the same order as `ids`. Ids which are missing
+/// in `media` result in an error.
+pub(crate) fn order_media_by_ids(
+    ids: &[String],
+    media: HashMap<String, Result<MediaCollection>>,
+) -> Vec<Result<MediaCollection>> {
+    ids.iter()
+        .map(|id| {
+            media.get(id).cloned().unwrap_or_else(|| {
+                Err(CrunchyrollError::Input(
+                    format!("failed to find valid media with id '{id}'").into(),
+                ))
+            })
+        })
+        .collect()
+}
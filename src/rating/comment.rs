@@ -8,6 +8,7 @@ use serde_json::json;
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct CommentUserAttributesAvatar {
@@ -16,6 +17,7 @@ pub struct CommentUserAttributesAvatar {
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct CommentUserAttributes {
@@ -25,6 +27,7 @@ pub struct CommentUserAttributes {
 
 /// Information about a user which wrote a [`Comment`].
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct CommentUser {
@@ -36,6 +39,7 @@ pub struct CommentUser {
 
 /// Number of votes users gave a [`Comment`].
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct CommentVotes {
@@ -57,6 +61,7 @@ enum_values! {
 
 /// Comment about a episode or movie.
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Comment {
@@ -74,6 +79,10 @@ pub struct Comment {
     pub message: String,
     #[serde(rename = "flags")]
     #[serde(deserialize_with = "deserialize_flags_to_spoiler")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "serialize_spoiler_to_flags")
+    )]
     pub is_spoiler: bool,
     /// If populated, this comment is deleted. Crunchyroll still stores deletes comments but without
     /// information what the content was (which means that [`Comment::message`] is not populated if
@@ -332,3 +341,13 @@ where
         ))),
     }
 }
+
+#[cfg(feature = "serde")]
+fn serialize_spoiler_to_flags<S: serde::Serializer>(
+    is_spoiler: &bool,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::Serialize;
+
+    if *is_spoiler { vec!["spoiler"] } else { vec![] }.serialize(serializer)
+}
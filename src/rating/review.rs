@@ -22,6 +22,7 @@ enum_values! {
 
 /// Details about a star rating of [`Rating`].
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct RatingStarDetails {
     /// The amount of user ratings.
     pub displayed: String,
@@ -37,6 +38,7 @@ pub struct RatingStarDetails {
 
 /// Overview about rating statistics for a series or movie listing.
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Rating {
@@ -53,14 +55,23 @@ pub struct Rating {
 
     pub total: u32,
     #[serde(deserialize_with = "crate::internal::serde::deserialize_try_from_string")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_to_string")
+    )]
     pub average: f64,
 
     #[serde(deserialize_with = "crate::internal::serde::deserialize_empty_pre_string_to_none")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::internal::serde::serialize_none_to_empty_string")
+    )]
     pub rating: Option<RatingStar>,
 }
 
 /// Ratings for a review a user has made about a series or movie listing.
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct ReviewRatings {
@@ -70,11 +81,13 @@ pub struct ReviewRatings {
 
     #[serde(rename = "rating")]
     #[serde(deserialize_with = "deserialize_rating_to_bool")]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_bool_to_rating"))]
     pub helpful: Option<bool>,
 }
 
 /// Content of a review a user has made about a series or movie listing.
 #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct ReviewContent {
@@ -96,6 +109,7 @@ pub struct ReviewContent {
 
 /// Author of a review.
 #[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct ReviewAuthor {
@@ -108,6 +122,7 @@ pub struct ReviewAuthor {
 
 /// A review a user has made about a series or movie listing.
 #[derive(Clone, Debug, Default, Deserialize, Request)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
 #[cfg_attr(not(feature = "__test_strict"), serde(default))]
 pub struct Review {
@@ -228,6 +243,18 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+fn serialize_bool_to_rating<S: serde::Serializer>(
+    helpful: &Option<bool>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match helpful {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "",
+    })
+}
+
 enum_values! {
     pub enum ReviewSortType {
         Newest = "newest"
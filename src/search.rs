@@ -2,9 +2,13 @@ mod browse {
     use crate::categories::Category;
     use crate::common::{Pagination, V2BulkResult};
     use crate::media::MediaType;
-    use crate::{enum_values, options, Crunchyroll, Locale, MediaCollection, Request, Result};
+    use crate::{
+        enum_values, options, Crunchyroll, Executor, Locale, MediaCollection, Request, Result,
+    };
+    use chrono::{DateTime, Datelike, Utc};
     use futures_util::FutureExt;
     use serde::Deserialize;
+    use std::sync::Arc;
 
     /// Human readable implementation of [`SimulcastSeason`].
     #[derive(Clone, Debug, Default, Deserialize)]
@@ -20,10 +24,75 @@ mod browse {
     #[cfg_attr(feature = "__test_strict", serde(deny_unknown_fields))]
     #[cfg_attr(not(feature = "__test_strict"), serde(default))]
     pub struct SimulcastSeason {
+        #[serde(skip)]
+        pub(crate) executor: Arc<Executor>,
+
         pub id: String,
         pub localization: SimulcastSeasonLocalization,
     }
 
+    /// Quarter of the year in which a [`SimulcastSeason`] airs.
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum SimulcastQuarter {
+        /// January to March.
+        Winter,
+        /// April to June.
+        Spring,
+        /// July to September.
+        Summer,
+        /// October to December.
+        Fall,
+    }
+
+    impl SimulcastQuarter {
+        /// Number of the quarter, from 1 ([`SimulcastQuarter::Winter`]) to 4
+        /// ([`SimulcastQuarter::Fall`]).
+        pub fn number(&self) -> u32 {
+            match self {
+                SimulcastQuarter::Winter => 1,
+                SimulcastQuarter::Spring => 2,
+                SimulcastQuarter::Summer => 3,
+                SimulcastQuarter::Fall => 4,
+            }
+        }
+
+        /// The quarter `date` is in.
+        pub fn from_date(date: &DateTime<Utc>) -> Self {
+            match date.month() {
+                1..=3 => SimulcastQuarter::Winter,
+                4..=6 => SimulcastQuarter::Spring,
+                7..=9 => SimulcastQuarter::Summer,
+                _ => SimulcastQuarter::Fall,
+            }
+        }
+    }
+
+    impl SimulcastSeason {
+        /// Parse the quarter and year of the season from its id (e.g. `fall-2023`). [`None`] if
+        /// the id has another format.
+        pub fn quarter_and_year(&self) -> Option<(SimulcastQuarter, u32)> {
+            let (quarter, year) = self.id.split_once('-')?;
+            let quarter = match quarter.to_lowercase().as_str() {
+                "winter" => SimulcastQuarter::Winter,
+                "spring" => SimulcastQuarter::Spring,
+                "summer" => SimulcastQuarter::Summer,
+                "fall" => SimulcastQuarter::Fall,
+                _ => return None,
+            };
+            Some((quarter, year.parse().ok()?))
+        }
+
+        /// The quarter of the season. See [`SimulcastSeason::quarter_and_year`].
+        pub fn quarter(&self) -> Option<SimulcastQuarter> {
+            self.quarter_and_year().map(|(quarter, _)| quarter)
+        }
+
+        /// The year of the season. See [`SimulcastSeason::quarter_and_year`].
+        pub fn year(&self) -> Option<u32> {
+            self.quarter_and_year().map(|(_, year)| year)
+        }
+    }
+
     #[allow(dead_code)]
     #[derive(Clone, Debug, Deserialize, smart_default::SmartDefault, Request)]
     #[request(executor(items))]
@@ -42,24 +111,40 @@ mod browse {
             Popularity = "popularity"
             NewlyAdded = "newly_added"
             Alphabetical = "alphabetical"
+            Rating = "rating"
         }
     }
 
     options! {
         BrowseOptions;
-        /// Specifies the categories of the entries.
+        /// Specifies the categories of the entries. Sub categories
+        /// ([`crate::categories::SubCategoryInformation`]) can be used too by converting them into
+        /// a [`Category`].
         categories(Vec<Category>, "categories") = None,
         /// Specifies whether the entries should be dubbed.
         is_dubbed(bool, "is_dubbed") = None,
         /// Specifies whether the entries should be subbed.
         is_subbed(bool, "is_subbed") = None,
         /// Specifies a particular simulcast season in which the entries should have been aired. Use
-        /// [`Crunchyroll::simulcast_seasons`] to get all seasons.
+        /// [`Crunchyroll::simulcast_seasons`] to get all seasons or
+        /// [`Crunchyroll::current_simulcast_season`] to get the currently airing one.
         simulcast_season(String, "season_tag") = None,
         /// Specifies how the entries should be sorted.
         sort(BrowseSortType, "sort") = Some(BrowseSortType::NewlyAdded),
         /// Specifies the media type of the entries.
-        media_type(MediaType, "type") = None
+        media_type(MediaType, "type") = None,
+        /// Specifies the audio locale the entries should be available in.
+        audio_locale(Locale, "audio_locale") = None,
+        /// Specifies the subtitle locale the entries should be available in.
+        subtitle_locale(Locale, "subtitle_locale") = None,
+        /// Specifies the earliest year in which the entries should have been launched.
+        launch_year_from(u32, "launch_year_start") = None,
+        /// Specifies the latest year in which the entries should have been launched.
+        launch_year_to(u32, "launch_year_end") = None,
+        /// Specifies the letters the titles of the entries should start with. Use `'#'` for titles
+        /// which start with a number or symbol. A range can be specified with e.g.
+        /// `('a'..='f').collect()`.
+        start_letters(Vec<char>, "start_letters") = None
     }
 
     impl Crunchyroll {
@@ -102,14 +187,66 @@ mod browse {
                 .await?
                 .items)
         }
+
+        /// Returns the simulcast season which is currently airing. If Crunchyroll has no season
+        /// for the current quarter (yet), the latest past season is returned. Is [`None`] if
+        /// Crunchyroll has no past or current simulcast season at all. The localization has the
+        /// language of the locale the client was built with.
+        pub async fn current_simulcast_season(&self) -> Result<Option<SimulcastSeason>> {
+            let now = Utc::now();
+            let current = (now.year() as u32, SimulcastQuarter::from_date(&now));
+
+            Ok(self
+                .simulcast_seasons(self.executor.details.locale.clone())
+                .await?
+                .into_iter()
+                .filter_map(|season| {
+                    let (quarter, year) = season.quarter_and_year()?;
+                    Some(((year, quarter), season))
+                })
+                .filter(|(year_quarter, _)| *year_quarter <= current)
+                .max_by_key(|(year_quarter, _)| *year_quarter)
+                .map(|(_, season)| season))
+        }
     }
 }
 
 mod query {
     use crate::common::{Pagination, V2BulkResult, V2TypeBulkResult};
-    use crate::media::{Episode, MovieListing, Series};
-    use crate::{Crunchyroll, MediaCollection, MusicVideo};
+    use crate::media::{Artist, Episode, MovieListing, Series};
+    use crate::{
+        enum_values, options, Concert, Crunchyroll, Locale, MediaCollection, MusicVideo, Request,
+        Result,
+    };
     use futures_util::FutureExt;
+    use serde::de::DeserializeOwned;
+
+    enum_values! {
+        pub enum SearchType {
+            TopResults = "top_results"
+            Series = "series"
+            MovieListing = "movie_listing"
+            Episode = "episode"
+            Music = "music"
+            Concert = "concert"
+            Artist = "artist"
+        }
+    }
+
+    options! {
+        SearchOptions;
+        /// Specifies the types of the results. Series, movie listings, episodes and music are
+        /// returned if not set.
+        types(Vec<SearchType>, "type") = None,
+        /// Specifies the audio locale the results should be available in.
+        audio_locale(Locale, "audio_locale") = None,
+        /// Specifies the subtitle locale the results should be available in.
+        subtitle_locale(Locale, "subtitle_locale") = None,
+        /// Specifies whether the results should be mature.
+        is_mature(bool, "is_mature") = None,
+        /// Specifies how many results of every type are requested at once.
+        page_size(u32, "limit") = None
+    }
 
     /// Results when querying Crunchyroll.
     pub struct QueryResults {
@@ -118,143 +255,189 @@ mod query {
         pub movie_listing: Pagination<MovieListing>,
         pub episode: Pagination<Episode>,
         pub music: Pagination<MusicVideo>,
+        pub concert: Pagination<Concert>,
+        pub artist: Pagination<Artist>,
+    }
+
+    /// A suggestion for a (partial) search query. Get it via [`Crunchyroll::search_suggestions`].
+    #[derive(Clone, Debug)]
+    pub struct SearchSuggestion {
+        /// Id of the suggested media.
+        pub id: String,
+        /// Title of the suggested media (the name if it's an artist), e.g. to display it as
+        /// completion of the query.
+        pub title: String,
+        pub search_type: SearchType,
+
+        /// The full suggested media.
+        pub media: MediaCollection,
+    }
+
+    impl From<MediaCollection> for SearchSuggestion {
+        fn from(media: MediaCollection) -> Self {
+            let (title, search_type) = match &media {
+                MediaCollection::Series(series) => (series.title.clone(), SearchType::Series),
+                MediaCollection::Season(season) => (season.title.clone(), SearchType::Series),
+                MediaCollection::Episode(episode) => (episode.title.clone(), SearchType::Episode),
+                MediaCollection::MovieListing(movie_listing) => {
+                    (movie_listing.title.clone(), SearchType::MovieListing)
+                }
+                MediaCollection::Movie(movie) => (movie.title.clone(), SearchType::MovieListing),
+                MediaCollection::MusicVideo(music_video) => {
+                    (music_video.title.clone(), SearchType::Music)
+                }
+                MediaCollection::Concert(concert) => (concert.title.clone(), SearchType::Concert),
+                MediaCollection::Artist(artist) => (artist.name.clone(), SearchType::Artist),
+            };
+            Self {
+                id: media.id().to_string(),
+                title,
+                search_type,
+                media,
+            }
+        }
     }
 
     impl Crunchyroll {
-        /// Search the Crunchyroll catalog by a given query / string.
+        /// Search the Crunchyroll catalog by a given query / string. Every type of result has its own
+        /// [`Pagination`] which requests the api separately, use [`Crunchyroll::search`] to get the
+        /// results of multiple types with one request per page.
         pub fn query<S: AsRef<str>>(&self, query: S) -> QueryResults {
             QueryResults {
-                top_results: Pagination::new(
-                    |options| {
-                        async move {
-                            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
-                            let result: V2BulkResult<V2TypeBulkResult<MediaCollection>> = options
-                                .executor
-                                .get(endpoint)
-                                .query(&[("q", options.extra.get("q").unwrap())])
-                                .query(&[("type", "top_results")])
-                                .query(&[("limit", options.page_size), ("start", options.start)])
-                                .apply_locale_query()
-                                .request()
-                                .await?;
-                            let top_results = result
-                                .data
-                                .into_iter()
-                                .find(|r| r.result_type == "top_results")
-                                .unwrap_or_default();
-                            Ok((top_results.items, top_results.total))
-                        }
-                        .boxed()
-                    },
-                    self.executor.clone(),
-                    None,
-                    Some(vec![("q", query.as_ref().to_string())]),
-                ),
-                series: Pagination::new(
-                    |options| {
-                        async move {
-                            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
-                            let result: V2BulkResult<V2TypeBulkResult<Series>> = options
-                                .executor
-                                .get(endpoint)
-                                .query(&[("q", options.extra.get("q").unwrap())])
-                                .query(&[("type", "series")])
-                                .query(&[("limit", options.page_size), ("start", options.start)])
-                                .apply_locale_query()
-                                .request()
-                                .await?;
-                            let series_results = result
-                                .data
-                                .into_iter()
-                                .find(|r| r.result_type == "series")
-                                .unwrap_or_default();
-                            Ok((series_results.items, series_results.total))
-                        }
-                        .boxed()
-                    },
-                    self.executor.clone(),
-                    None,
-                    Some(vec![("q", query.as_ref().to_string())]),
-                ),
-                movie_listing: Pagination::new(
-                    |options| {
-                        async move {
-                            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
-                            let result: V2BulkResult<V2TypeBulkResult<MovieListing>> = options
-                                .executor
-                                .get(endpoint)
-                                .query(&[("q", options.extra.get("q").unwrap())])
-                                .query(&[("type", "movie_listing")])
-                                .query(&[("limit", options.page_size), ("start", options.start)])
-                                .apply_locale_query()
-                                .request()
-                                .await?;
-                            let movie_listing_results = result
-                                .data
-                                .into_iter()
-                                .find(|r| r.result_type == "movie_listing")
-                                .unwrap_or_default();
-                            Ok((movie_listing_results.items, movie_listing_results.total))
-                        }
-                        .boxed()
-                    },
-                    self.executor.clone(),
-                    None,
-                    Some(vec![("q", query.as_ref().to_string())]),
-                ),
-                episode: Pagination::new(
-                    |options| {
-                        async move {
-                            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
-                            let result: V2BulkResult<V2TypeBulkResult<Episode>> = options
-                                .executor
-                                .get(endpoint)
-                                .query(&[("q", options.extra.get("q").unwrap())])
-                                .query(&[("type", "episode")])
-                                .query(&[("limit", options.page_size), ("start", options.start)])
-                                .apply_locale_query()
-                                .request()
-                                .await?;
-                            let episode_results = result
-                                .data
-                                .into_iter()
-                                .find(|r| r.result_type == "episode")
-                                .unwrap_or_default();
-                            Ok((episode_results.items, episode_results.total))
-                        }
-                        .boxed()
-                    },
-                    self.executor.clone(),
-                    None,
-                    Some(vec![("q", query.as_ref().to_string())]),
-                ),
-                music: Pagination::new(
-                    |options| {
-                        async move {
-                            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
-                            let result: V2BulkResult<V2TypeBulkResult<MusicVideo>> = options
-                                .executor
-                                .get(endpoint)
-                                .query(&[("q", options.extra.get("q").unwrap())])
-                                .query(&[("type", "music")])
-                                .query(&[("limit", options.page_size), ("start", options.start)])
-                                .apply_locale_query()
-                                .request()
-                                .await?;
-                            let music_results = result
-                                .data
-                                .into_iter()
-                                .find(|r| r.result_type == "episode")
-                                .unwrap_or_default();
-                            Ok((music_results.items, music_results.total))
+                top_results: self.query_type(query.as_ref(), "top_results"),
+                series: self.query_type(query.as_ref(), "series"),
+                movie_listing: self.query_type(query.as_ref(), "movie_listing"),
+                episode: self.query_type(query.as_ref(), "episode"),
+                music: self.query_type(query.as_ref(), "music"),
+                concert: self.query_type(query.as_ref(), "concert"),
+                artist: self.query_type(query.as_ref(), "artist"),
+            }
+        }
+
+        /// Search the Crunchyroll catalog for results of type `result_type`.
+        fn query_type<T: Default + DeserializeOwned + Request + 'static>(
+            &self,
+            query: &str,
+            result_type: &'static str,
+        ) -> Pagination<T> {
+            Pagination::new(
+                move |options| {
+                    async move {
+                        let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
+                        let result: V2BulkResult<V2TypeBulkResult<T>> = options
+                            .executor
+                            .get(endpoint)
+                            .query(&[("q", options.extra.get("q").unwrap())])
+                            .query(&[("type", result_type)])
+                            .query(&[("limit", options.page_size), ("start", options.start)])
+                            .apply_locale_query()
+                            .apply_preferred_audio_locale_query()
+                            .request()
+                            .await?;
+                        let results = result
+                            .data
+                            .into_iter()
+                            .find(|r| r.result_type == result_type)
+                            .unwrap_or_default();
+                        Ok((results.items, results.total))
+                    }
+                    .boxed()
+                },
+                self.executor.clone(),
+                None,
+                Some(vec![("q", query.to_string())]),
+            )
+        }
+
+        /// Search the Crunchyroll catalog by a given query / string, filtered by the specified
+        /// options. The results of all requested types are returned in one [`Pagination`], every
+        /// page contains up to [`SearchOptions::page_size`] results of every type.
+        pub fn search<S: AsRef<str>>(
+            &self,
+            query: S,
+            mut options: SearchOptions,
+        ) -> Pagination<MediaCollection> {
+            if options.types.is_none() {
+                options.types = Some(vec![
+                    SearchType::Series,
+                    SearchType::MovieListing,
+                    SearchType::Episode,
+                    SearchType::Music,
+                ])
+            }
+            let page_size = options.page_size.take();
+
+            let mut pagination = Pagination::new(
+                |options| {
+                    async move {
+                        let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
+                        let result: V2BulkResult<V2TypeBulkResult<MediaCollection>> = options
+                            .executor
+                            .get(endpoint)
+                            .query(&[("q", options.extra.get("q").unwrap())])
+                            .query(&options.query)
+                            // the offset applies to every result type separately, so it must be
+                            // calculated from the page and not from the count of already returned
+                            // results
+                            .query(&[
+                                ("limit", options.page_size),
+                                ("start", (options.page - 1) * options.page_size),
+                            ])
+                            .apply_locale_query()
+                            .apply_preferred_audio_locale_query()
+                            .request()
+                            .await?;
+
+                        let total = result.data.iter().map(|r| r.total).sum();
+                        let items: Vec<MediaCollection> =
+                            result.data.into_iter().flat_map(|r| r.items).collect();
+                        // stop if the api doesn't return any more results, even if the total says
+                        // that more results are available
+                        if items.is_empty() {
+                            return Ok((items, options.start));
                         }
-                        .boxed()
-                    },
-                    self.executor.clone(),
-                    None,
-                    Some(vec![("q", query.as_ref().to_string())]),
-                ),
+                        Ok((items, total))
+                    }
+                    .boxed()
+                },
+                self.executor.clone(),
+                Some(options.into_query()),
+                Some(vec![("q", query.as_ref().to_string())]),
+            );
+            if let Some(page_size) = page_size {
+                pagination.page_size(page_size)
             }
+            pagination
+        }
+
+        /// Return up to `limit` suggestions for a (partial) query, e.g. to provide autocompletion
+        /// while typing. The suggestions are the top results of the query, ordered by relevance.
+        /// Only one request is made, so it's faster than [`Crunchyroll::search`].
+        pub async fn search_suggestions<S: AsRef<str>>(
+            &self,
+            query: S,
+            limit: u32,
+        ) -> Result<Vec<SearchSuggestion>> {
+            let endpoint = "https://www.crunchyroll.com/content/v2/discover/search";
+            let result: V2BulkResult<V2TypeBulkResult<MediaCollection>> = self
+                .executor
+                .get(endpoint)
+                .query(&[("q", query.as_ref())])
+                .query(&[("type", "top_results")])
+                .query(&[("limit", limit)])
+                .apply_locale_query()
+                .apply_preferred_audio_locale_query()
+                .request()
+                .await?;
+            Ok(result
+                .data
+                .into_iter()
+                .find(|r| r.result_type == "top_results")
+                .unwrap_or_default()
+                .items
+                .into_iter()
+                .map(SearchSuggestion::from)
+                .collect())
         }
     }
 }
@@ -0,0 +1,515 @@
+//! Export media into a normalized SQLite database.
+
+use crate::categories::Category;
+use crate::common::Image;
+use crate::crawler::CrawlSink;
+use crate::error::CrunchyrollError;
+use crate::{
+    Crunchyroll, Episode, Locale, MediaCollection, Movie, MovieListing, Result, Season, Series,
+};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Transaction};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS series (
+    id TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    slug_title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    content_provider TEXT,
+    launch_year INTEGER,
+    episode_count INTEGER NOT NULL,
+    season_count INTEGER NOT NULL,
+    is_subbed INTEGER NOT NULL,
+    is_dubbed INTEGER NOT NULL,
+    is_simulcast INTEGER NOT NULL,
+    is_mature INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS seasons (
+    id TEXT PRIMARY KEY NOT NULL,
+    series_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    slug_title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    season_number INTEGER NOT NULL,
+    season_sequence_number INTEGER NOT NULL,
+    number_of_episodes INTEGER NOT NULL,
+    is_complete INTEGER NOT NULL,
+    is_subbed INTEGER NOT NULL,
+    is_dubbed INTEGER NOT NULL,
+    is_simulcast INTEGER NOT NULL,
+    is_mature INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS seasons_series_id ON seasons (series_id);
+CREATE TABLE IF NOT EXISTS episodes (
+    id TEXT PRIMARY KEY NOT NULL,
+    series_id TEXT NOT NULL,
+    season_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    slug_title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    episode TEXT NOT NULL,
+    episode_number INTEGER NOT NULL,
+    sequence_number REAL NOT NULL,
+    season_number INTEGER NOT NULL,
+    audio_locale TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    episode_air_date TEXT NOT NULL,
+    premium_available_date TEXT NOT NULL,
+    free_available_date TEXT NOT NULL,
+    is_premium_only INTEGER NOT NULL,
+    is_clip INTEGER NOT NULL,
+    is_mature INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS episodes_season_id ON episodes (season_id);
+CREATE INDEX IF NOT EXISTS episodes_series_id ON episodes (series_id);
+CREATE TABLE IF NOT EXISTS movie_listings (
+    id TEXT PRIMARY KEY NOT NULL,
+    title TEXT NOT NULL,
+    slug_title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    content_provider TEXT,
+    release_year INTEGER NOT NULL,
+    audio_locale TEXT,
+    is_subbed INTEGER NOT NULL,
+    is_dubbed INTEGER NOT NULL,
+    is_premium_only INTEGER NOT NULL,
+    is_mature INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS movies (
+    id TEXT PRIMARY KEY NOT NULL,
+    movie_listing_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    slug_title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    is_subbed INTEGER NOT NULL,
+    is_dubbed INTEGER NOT NULL,
+    is_premium_only INTEGER NOT NULL,
+    is_mature INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS movies_movie_listing_id ON movies (movie_listing_id);
+CREATE TABLE IF NOT EXISTS versions (
+    media_id TEXT NOT NULL,
+    version_id TEXT NOT NULL,
+    audio_locale TEXT NOT NULL,
+    original INTEGER NOT NULL,
+    variant TEXT NOT NULL,
+    PRIMARY KEY (media_id, version_id)
+);
+CREATE TABLE IF NOT EXISTS locales (
+    media_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    locale TEXT NOT NULL,
+    PRIMARY KEY (media_id, kind, locale)
+);
+CREATE TABLE IF NOT EXISTS categories (
+    media_id TEXT NOT NULL,
+    category TEXT NOT NULL,
+    PRIMARY KEY (media_id, category)
+);
+CREATE TABLE IF NOT EXISTS season_tags (
+    media_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (media_id, tag)
+);
+CREATE TABLE IF NOT EXISTS images (
+    media_id TEXT NOT NULL,
+    image_type TEXT NOT NULL,
+    source TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    PRIMARY KEY (media_id, source)
+);
+";
+
+/// Tables which reference a media by its id and get replaced on every export of the media.
+const CHILD_TABLES: [&str; 5] = ["versions", "locales", "categories", "season_tags", "images"];
+
+/// Writes [`Series`], [`Season`], [`Episode`], [`MovieListing`] and [`Movie`] with their
+/// versions, locales, categories, season tags and images into a SQLite database.
+///
+/// Every media gets its own row in the `series`, `seasons`, `episodes`, `movie_listings` or
+/// `movies` table. Versions, audio / subtitle locales (distinguished by the `kind` column),
+/// categories, season tags and images are stored in separate tables which reference the media via
+/// their `media_id` column. Exporting a media which already exists in the database replaces it
+/// and all its referencing rows, so the same database can be updated incrementally.
+///
+/// The exporter implements [`CrawlSink`], so it can be used to export a whole
+/// [`crate::crawler::Crawler`] run. Media which is written to the sink is buffered and exported in
+/// one transaction when [`CrawlSink::flush`] is called, which the crawler does after every series /
+/// movie listing.
+pub struct SqliteExporter {
+    connection: Connection,
+
+    /// Media which was written via [`CrawlSink::write`] but not flushed yet.
+    pending: Vec<MediaCollection>,
+}
+
+impl SqliteExporter {
+    /// Open (or create) the database at `path` and create all tables which don't exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Use an already opened connection, e.g. an in-memory database. All tables which don't exist
+    /// yet are created.
+    pub fn from_connection(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection,
+            pending: vec![],
+        })
+    }
+
+    /// The underlying database connection. Use it to query the exported data.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Return the underlying database connection.
+    pub fn into_inner(self) -> Connection {
+        self.connection
+    }
+
+    /// Insert or replace `media`. Music videos, concerts and artists are ignored.
+    pub fn export(&mut self, media: &MediaCollection) -> Result<()> {
+        self.export_all([media]).map(|_| ())
+    }
+
+    /// Insert or replace all `media` in a single transaction, which is much faster than exporting
+    /// them one by one. If one media fails to export, none of them are exported. Music videos,
+    /// concerts and artists are ignored. Returns the number of exported media.
+    pub fn export_all<'a>(
+        &mut self,
+        media: impl IntoIterator<Item = &'a MediaCollection>,
+    ) -> Result<usize> {
+        let tx = self.connection.transaction()?;
+        let mut count = 0;
+        for m in media {
+            match m {
+                MediaCollection::Series(series) => export_series(&tx, series)?,
+                MediaCollection::Season(season) => export_season(&tx, season)?,
+                MediaCollection::Episode(episode) => export_episode(&tx, episode)?,
+                MediaCollection::MovieListing(movie_listing) => {
+                    export_movie_listing(&tx, movie_listing)?
+                }
+                MediaCollection::Movie(movie) => export_movie(&tx, movie)?,
+                MediaCollection::MusicVideo(_)
+                | MediaCollection::Concert(_)
+                | MediaCollection::Artist(_) => continue,
+            }
+            count += 1
+        }
+        tx.commit()?;
+        Ok(count)
+    }
+
+    /// Request the media with the given ids and export them. Series and movie listings are
+    /// exported together with all their seasons, episodes and movies, every one of them in a
+    /// single transaction. Ids which don't exist or can't be requested don't abort the export,
+    /// they are listed in the returned [`SqliteExportReport`]. An error is only returned if
+    /// writing to the database fails.
+    pub async fn export_ids<S: AsRef<str>>(
+        &mut self,
+        crunchyroll: &Crunchyroll,
+        ids: &[S],
+    ) -> Result<SqliteExportReport> {
+        let crawler = crunchyroll.crawler();
+        let mut report = SqliteExportReport::default();
+        for (id, media) in ids
+            .iter()
+            .zip(MediaCollection::from_ids(crunchyroll, ids).await)
+        {
+            let id = id.as_ref().to_string();
+            let crawled = match media {
+                Ok(media) => crawler.crawl(media).await,
+                Err(e) => Err(e),
+            };
+            match crawled {
+                Ok(crawled) => report.exported += self.export_all(&crawled)?,
+                Err(CrunchyrollError::Input(_)) => report.unknown.push(id),
+                Err(e) => report.failed.push((id, e)),
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[async_trait::async_trait]
+impl CrawlSink for SqliteExporter {
+    async fn write(&mut self, media: MediaCollection) -> Result<()> {
+        self.pending.push(media);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.export_all(&pending)?;
+        Ok(())
+    }
+}
+
+/// Result of [`SqliteExporter::export_ids`].
+#[derive(Clone, Debug, Default)]
+pub struct SqliteExportReport {
+    /// Number of exported series, seasons, episodes, movie listings and movies. Music videos,
+    /// concerts and artists aren't exported and therefore not counted.
+    pub exported: usize,
+    /// Ids which don't exist.
+    pub unknown: Vec<String>,
+    /// Ids whose media or children could not be requested and the error which occurred.
+    pub failed: Vec<(String, CrunchyrollError)>,
+}
+
+fn date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339()
+}
+
+fn clear_children(tx: &Transaction, media_id: &str) -> Result<()> {
+    for table in CHILD_TABLES {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE media_id = ?1", table),
+            [media_id],
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_locales(tx: &Transaction, media_id: &str, kind: &str, locales: &[Locale]) -> Result<()> {
+    let mut stmt = tx.prepare_cached(
+        "INSERT OR IGNORE INTO locales (media_id, kind, locale) VALUES (?1, ?2, ?3)",
+    )?;
+    for locale in locales {
+        stmt.execute(params![media_id, kind, locale.to_string()])?;
+    }
+    Ok(())
+}
+
+fn insert_categories(tx: &Transaction, media_id: &str, categories: &[Category]) -> Result<()> {
+    let mut stmt =
+        tx.prepare_cached("INSERT OR IGNORE INTO categories (media_id, category) VALUES (?1, ?2)")?;
+    for category in categories {
+        stmt.execute(params![media_id, category.to_string()])?;
+    }
+    Ok(())
+}
+
+fn insert_season_tags(tx: &Transaction, media_id: &str, tags: &[String]) -> Result<()> {
+    let mut stmt =
+        tx.prepare_cached("INSERT OR IGNORE INTO season_tags (media_id, tag) VALUES (?1, ?2)")?;
+    for tag in tags {
+        stmt.execute(params![media_id, tag])?;
+    }
+    Ok(())
+}
+
+fn insert_images<'a>(
+    tx: &Transaction,
+    media_id: &str,
+    images: impl Iterator<Item = &'a Image>,
+) -> Result<()> {
+    let mut stmt = tx.prepare_cached(
+        "INSERT OR IGNORE INTO images (media_id, image_type, source, width, height) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for image in images {
+        stmt.execute(params![
+            media_id,
+            image.image_type,
+            image.source,
+            image.width,
+            image.height
+        ])?;
+    }
+    Ok(())
+}
+
+fn insert_version(
+    tx: &Transaction,
+    media_id: &str,
+    version_id: &str,
+    audio_locale: &Locale,
+    original: bool,
+    variant: &str,
+) -> Result<()> {
+    tx.prepare_cached(
+        "INSERT OR IGNORE INTO versions (media_id, version_id, audio_locale, original, variant) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?
+    .execute(params![
+        media_id,
+        version_id,
+        audio_locale.to_string(),
+        original,
+        variant
+    ])?;
+    Ok(())
+}
+
+fn export_series(tx: &Transaction, series: &Series) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO series (id, title, slug_title, description, content_provider, launch_year, episode_count, season_count, is_subbed, is_dubbed, is_simulcast, is_mature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            series.id,
+            series.title,
+            series.slug_title,
+            series.description,
+            series.content_provider,
+            series.series_launch_year,
+            series.episode_count,
+            series.season_count,
+            series.is_subbed,
+            series.is_dubbed,
+            series.is_simulcast,
+            series.is_mature
+        ],
+    )?;
+    clear_children(tx, &series.id)?;
+    insert_locales(tx, &series.id, "audio", &series.audio_locales)?;
+    insert_locales(tx, &series.id, "subtitle", &series.subtitle_locales)?;
+    insert_categories(tx, &series.id, &series.categories)?;
+    insert_season_tags(tx, &series.id, &series.season_tags)?;
+    insert_images(
+        tx,
+        &series.id,
+        series
+            .images
+            .poster_tall
+            .iter()
+            .chain(series.images.poster_wide.iter()),
+    )
+}
+
+fn export_season(tx: &Transaction, season: &Season) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO seasons (id, series_id, title, slug_title, description, season_number, season_sequence_number, number_of_episodes, is_complete, is_subbed, is_dubbed, is_simulcast, is_mature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            season.id,
+            season.series_id,
+            season.title,
+            season.slug_title,
+            season.description,
+            season.season_number,
+            season.season_sequence_number,
+            season.number_of_episodes,
+            season.is_complete,
+            season.is_subbed,
+            season.is_dubbed,
+            season.is_simulcast,
+            season.is_mature
+        ],
+    )?;
+    clear_children(tx, &season.id)?;
+    insert_locales(tx, &season.id, "audio", &season.audio_locales)?;
+    insert_locales(tx, &season.id, "subtitle", &season.subtitle_locales)?;
+    insert_season_tags(tx, &season.id, &season.season_tags)?;
+    for version in season.versions.iter().flatten() {
+        insert_version(
+            tx,
+            &season.id,
+            &version.id,
+            &version.audio_locale,
+            version.original,
+            &version.variant,
+        )?
+    }
+    Ok(())
+}
+
+fn export_episode(tx: &Transaction, episode: &Episode) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO episodes (id, series_id, season_id, title, slug_title, description, episode, episode_number, sequence_number, season_number, audio_locale, duration_ms, episode_air_date, premium_available_date, free_available_date, is_premium_only, is_clip, is_mature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+        params![
+            episode.id,
+            episode.series_id,
+            episode.season_id,
+            episode.title,
+            episode.slug_title,
+            episode.description,
+            episode.episode,
+            episode.episode_number,
+            episode.sequence_number,
+            episode.season_number,
+            episode.audio_locale.to_string(),
+            episode.duration.num_milliseconds(),
+            date(&episode.episode_air_date),
+            date(&episode.premium_available_date),
+            date(&episode.free_available_date),
+            episode.is_premium_only,
+            episode.is_clip,
+            episode.is_mature
+        ],
+    )?;
+    clear_children(tx, &episode.id)?;
+    insert_locales(tx, &episode.id, "subtitle", &episode.subtitle_locales)?;
+    insert_season_tags(tx, &episode.id, &episode.season_tags)?;
+    insert_images(tx, &episode.id, episode.images.iter())?;
+    for version in episode.versions.iter().flatten() {
+        insert_version(
+            tx,
+            &episode.id,
+            &version.id,
+            &version.audio_locale,
+            version.original,
+            &version.variant,
+        )?
+    }
+    Ok(())
+}
+
+fn export_movie_listing(tx: &Transaction, movie_listing: &MovieListing) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO movie_listings (id, title, slug_title, description, content_provider, release_year, audio_locale, is_subbed, is_dubbed, is_premium_only, is_mature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            movie_listing.id,
+            movie_listing.title,
+            movie_listing.slug_title,
+            movie_listing.description,
+            movie_listing.content_provider,
+            movie_listing.movie_release_year,
+            movie_listing.audio_locale.as_ref().map(|l| l.to_string()),
+            movie_listing.is_subbed,
+            movie_listing.is_dubbed,
+            movie_listing.is_premium_only,
+            movie_listing.is_mature
+        ],
+    )?;
+    clear_children(tx, &movie_listing.id)?;
+    insert_locales(
+        tx,
+        &movie_listing.id,
+        "subtitle",
+        &movie_listing.subtitle_locales,
+    )?;
+    insert_categories(tx, &movie_listing.id, &movie_listing.categories)?;
+    insert_season_tags(tx, &movie_listing.id, &movie_listing.season_tags)?;
+    insert_images(
+        tx,
+        &movie_listing.id,
+        movie_listing
+            .images
+            .poster_tall
+            .iter()
+            .chain(movie_listing.images.poster_wide.iter()),
+    )
+}
+
+fn export_movie(tx: &Transaction, movie: &Movie) -> Result<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO movies (id, movie_listing_id, title, slug_title, description, duration_ms, is_subbed, is_dubbed, is_premium_only, is_mature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            movie.id,
+            movie.movie_listing_id,
+            movie.title,
+            movie.slug_title,
+            movie.description,
+            movie.duration.num_milliseconds(),
+            movie.is_subbed,
+            movie.is_dubbed,
+            movie.is_premium_only,
+            movie.is_mature
+        ],
+    )?;
+    clear_children(tx, &movie.id)?;
+    insert_images(tx, &movie.id, movie.images.thumbnail.iter())
+}
@@ -0,0 +1,391 @@
+//! Detect changes of series, like new episodes or dubs, by comparing snapshots of them.
+
+use crate::error::CrunchyrollError;
+use crate::list::{WatchlistEntry, WatchlistOptions};
+use crate::media::util::MAX_CONCURRENT_REQUESTS;
+use crate::media::MediaType;
+use crate::{Crunchyroll, Episode, Locale, MediaCollection, Result, Series};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// State of an episode at the time a [`SeriesSnapshot`] was taken.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpisodeSnapshot {
+    pub id: String,
+    pub season_id: String,
+    pub title: String,
+
+    pub episode_number: u32,
+    /// See [`crate::Episode::sequence_number`].
+    pub sequence_number: f32,
+
+    pub audio_locale: Locale,
+    pub subtitle_locales: Vec<Locale>,
+
+    pub is_premium_only: bool,
+}
+
+/// State of a season and its episodes at the time a [`SeriesSnapshot`] was taken.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonSnapshot {
+    pub id: String,
+    pub title: String,
+    pub season_number: u32,
+
+    pub audio_locales: Vec<Locale>,
+    pub subtitle_locales: Vec<Locale>,
+
+    pub episodes: Vec<EpisodeSnapshot>,
+}
+
+/// State of a series, its seasons and episodes at a specific time.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeriesSnapshot {
+    pub id: String,
+    pub title: String,
+
+    pub audio_locales: Vec<Locale>,
+    pub subtitle_locales: Vec<Locale>,
+
+    pub seasons: Vec<SeasonSnapshot>,
+
+    pub taken_at: DateTime<Utc>,
+}
+
+impl SeriesSnapshot {
+    /// Take a snapshot of `series`. A limited number of seasons are requested concurrently.
+    pub async fn take(series: &Series) -> Result<Self> {
+        let seasons = series.seasons().await?;
+        let episodes: Vec<Vec<Episode>> = stream::iter(seasons.iter().map(|s| s.episodes()))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        Ok(Self {
+            id: series.id.clone(),
+            title: series.title.clone(),
+            audio_locales: series.audio_locales.clone(),
+            subtitle_locales: series.subtitle_locales.clone(),
+            seasons: seasons
+                .into_iter()
+                .zip(episodes)
+                .map(|(season, episodes)| SeasonSnapshot {
+                    id: season.id,
+                    title: season.title,
+                    season_number: season.season_number,
+                    audio_locales: season.audio_locales,
+                    subtitle_locales: season.subtitle_locales,
+                    episodes: episodes
+                        .into_iter()
+                        .map(|episode| EpisodeSnapshot {
+                            id: episode.id,
+                            season_id: episode.season_id,
+                            title: episode.title,
+                            episode_number: episode.episode_number,
+                            sequence_number: episode.sequence_number,
+                            audio_locale: episode.audio_locale,
+                            subtitle_locales: episode.subtitle_locales,
+                            is_premium_only: episode.is_premium_only,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            taken_at: Utc::now(),
+        })
+    }
+
+    /// Compare this (newer) snapshot with an `old` snapshot of the same series and return all
+    /// changes between them. Episodes of a new season only result in a
+    /// [`WatchEvent::NewSeason`] and not in an additional [`WatchEvent::NewEpisode`] each.
+    pub fn diff(&self, old: &SeriesSnapshot) -> Vec<WatchEvent> {
+        let mut events = vec![];
+
+        for locale in &self.audio_locales {
+            if !old.audio_locales.contains(locale) {
+                events.push(WatchEvent::NewDubLocale {
+                    series_id: self.id.clone(),
+                    locale: locale.clone(),
+                })
+            }
+        }
+        for locale in &self.subtitle_locales {
+            if !old.subtitle_locales.contains(locale) {
+                events.push(WatchEvent::NewSubtitleLocale {
+                    series_id: self.id.clone(),
+                    locale: locale.clone(),
+                })
+            }
+        }
+
+        for season in &self.seasons {
+            let old_season = match old.seasons.iter().find(|s| s.id == season.id) {
+                Some(old_season) => old_season,
+                None => {
+                    events.push(WatchEvent::NewSeason {
+                        series_id: self.id.clone(),
+                        season: season.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            for episode in &season.episodes {
+                match old_season.episodes.iter().find(|e| e.id == episode.id) {
+                    Some(old_episode) => {
+                        if old_episode.is_premium_only && !episode.is_premium_only {
+                            events.push(WatchEvent::NowFree {
+                                series_id: self.id.clone(),
+                                episode: episode.clone(),
+                            })
+                        }
+                    }
+                    None => events.push(WatchEvent::NewEpisode {
+                        series_id: self.id.clone(),
+                        episode: episode.clone(),
+                    }),
+                }
+            }
+            for old_episode in &old_season.episodes {
+                if !season.episodes.iter().any(|e| e.id == old_episode.id) {
+                    events.push(WatchEvent::EpisodeRemoved {
+                        series_id: self.id.clone(),
+                        episode: old_episode.clone(),
+                    })
+                }
+            }
+        }
+        for old_season in &old.seasons {
+            if !self.seasons.iter().any(|s| s.id == old_season.id) {
+                events.push(WatchEvent::SeasonRemoved {
+                    series_id: self.id.clone(),
+                    season: old_season.clone(),
+                })
+            }
+        }
+
+        events
+    }
+}
+
+/// A change which was detected between two [`SeriesSnapshot`]s.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WatchEvent {
+    NewEpisode {
+        series_id: String,
+        episode: EpisodeSnapshot,
+    },
+    /// Dubs are mostly separate seasons, so a new dub usually also results in a
+    /// [`WatchEvent::NewDubLocale`] if the series hadn't the locale before.
+    NewSeason {
+        series_id: String,
+        season: SeasonSnapshot,
+    },
+    NewDubLocale {
+        series_id: String,
+        locale: Locale,
+    },
+    NewSubtitleLocale {
+        series_id: String,
+        locale: Locale,
+    },
+    /// The episode was premium only and is now available for free.
+    NowFree {
+        series_id: String,
+        episode: EpisodeSnapshot,
+    },
+    EpisodeRemoved {
+        series_id: String,
+        episode: EpisodeSnapshot,
+    },
+    SeasonRemoved {
+        series_id: String,
+        season: SeasonSnapshot,
+    },
+    /// The series doesn't exist anymore or isn't available in your region anymore. Contains the
+    /// last stored snapshot of it.
+    SeriesRemoved {
+        series_id: String,
+        series: SeriesSnapshot,
+    },
+}
+
+/// Storage for [`SeriesSnapshot`]s which are used by [`Watcher`]. Implement it to persist the
+/// snapshots, e.g. in a file or database, so that changes are detected across restarts.
+#[async_trait::async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// Return the latest stored snapshot of the series with id `series_id`.
+    async fn load(&self, series_id: &str) -> Result<Option<SeriesSnapshot>>;
+
+    /// Store `snapshot`, replacing any previous snapshot of the same series.
+    async fn save(&self, snapshot: SeriesSnapshot) -> Result<()>;
+
+    /// Remove the snapshot of the series with id `series_id`. Called after the series was removed.
+    async fn remove(&self, series_id: &str) -> Result<()>;
+}
+
+/// [`SnapshotStore`] which keeps all snapshots in memory.
+#[derive(Debug, Default)]
+pub struct MemorySnapshotStore {
+    snapshots: Mutex<HashMap<String, SeriesSnapshot>>,
+}
+
+#[async_trait::async_trait]
+impl SnapshotStore for MemorySnapshotStore {
+    async fn load(&self, series_id: &str) -> Result<Option<SeriesSnapshot>> {
+        Ok(self.snapshots.lock().unwrap().get(series_id).cloned())
+    }
+
+    async fn save(&self, snapshot: SeriesSnapshot) -> Result<()> {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(snapshot.id.clone(), snapshot);
+        Ok(())
+    }
+
+    async fn remove(&self, series_id: &str) -> Result<()> {
+        self.snapshots.lock().unwrap().remove(series_id);
+        Ok(())
+    }
+}
+
+/// Which series a [`Watcher`] should watch.
+#[derive(Clone, Debug)]
+pub enum WatchTarget {
+    /// Series ids.
+    Series(Vec<String>),
+    /// All series which are on your watchlist at the time of polling.
+    Watchlist,
+}
+
+/// Result of [`Watcher::poll`].
+#[derive(Clone, Debug, Default)]
+pub struct PollResult {
+    pub events: Vec<WatchEvent>,
+    /// Ids of series which could not be polled and the error which occurred. Their stored snapshots
+    /// are kept, so their changes are emitted at the next successful poll.
+    pub failed: Vec<(String, CrunchyrollError)>,
+}
+
+/// Polls series and emits [`WatchEvent`]s for everything which changed since the last poll. Get
+/// it via [`Crunchyroll::watcher`].
+pub struct Watcher<S: SnapshotStore> {
+    crunchyroll: Crunchyroll,
+
+    target: WatchTarget,
+    store: S,
+    concurrency: usize,
+}
+
+impl<S: SnapshotStore> Watcher<S> {
+    /// How many series are polled at the same time. Default is 4.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Return the store which holds the snapshots.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Take a new snapshot of every watched series, compare it with the stored one and save it
+    /// afterwards. Series which have no stored snapshot yet (e.g. at the first poll) don't emit
+    /// any events. Series which fail to poll don't affect the other ones, they are listed in
+    /// [`PollResult::failed`]. An error is only returned if the watchlist can't be requested when
+    /// watching [`WatchTarget::Watchlist`].
+    pub async fn poll(&self) -> Result<PollResult> {
+        let mut result = PollResult::default();
+
+        let series = match &self.target {
+            WatchTarget::Series(ids) => {
+                let mut series = vec![];
+                let requested = self.crunchyroll.media_from_ids::<Series>(ids).await;
+                for (id, requested_series) in ids.iter().zip(requested) {
+                    match requested_series {
+                        Ok(s) => series.push(s),
+                        // the series wasn't found, so it was either removed or the id is invalid
+                        Err(CrunchyrollError::Input(context)) => match self.remove_series(id).await
+                        {
+                            Ok(Some(event)) => result.events.push(event),
+                            Ok(None) => result
+                                .failed
+                                .push((id.clone(), CrunchyrollError::Input(context))),
+                            Err(err) => result.failed.push((id.clone(), err)),
+                        },
+                        Err(err) => result.failed.push((id.clone(), err)),
+                    }
+                }
+                series
+            }
+            WatchTarget::Watchlist => self
+                .crunchyroll
+                .watchlist(WatchlistOptions::default().media_type(MediaType::Series))
+                .try_collect::<Vec<WatchlistEntry>>()
+                .await?
+                .into_iter()
+                .filter_map(|entry| match entry.panel {
+                    MediaCollection::Series(series) => Some(series),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        let mut polled = stream::iter(
+            series
+                .iter()
+                .map(|s| async move { (s.id.clone(), self.poll_series(s).await) }),
+        )
+        .buffered(self.concurrency);
+        while let Some((id, events)) = polled.next().await {
+            match events {
+                Ok(events) => result.events.extend(events),
+                Err(err) => result.failed.push((id, err)),
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn poll_series(&self, series: &Series) -> Result<Vec<WatchEvent>> {
+        let snapshot = SeriesSnapshot::take(series).await?;
+        let events = match self.store.load(&snapshot.id).await? {
+            Some(old) => snapshot.diff(&old),
+            None => vec![],
+        };
+        self.store.save(snapshot).await?;
+        Ok(events)
+    }
+
+    /// Remove the stored snapshot of a series which doesn't exist anymore. Returns [`None`] if no
+    /// snapshot of it is stored.
+    async fn remove_series(&self, series_id: &str) -> Result<Option<WatchEvent>> {
+        let series = match self.store.load(series_id).await? {
+            Some(series) => series,
+            None => return Ok(None),
+        };
+        self.store.remove(series_id).await?;
+        Ok(Some(WatchEvent::SeriesRemoved {
+            series_id: series_id.to_string(),
+            series,
+        }))
+    }
+}
+
+impl Crunchyroll {
+    /// Create a [`Watcher`] which watches `target` and stores its snapshots in `store`.
+    pub fn watcher<S: SnapshotStore>(&self, target: WatchTarget, store: S) -> Watcher<S> {
+        Watcher {
+            crunchyroll: self.clone(),
+            target,
+            store,
+            concurrency: 4,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::SESSION;
+use chrono::{Duration, Utc};
+use crunchyroll_rs::Locale;
+
+mod utils;
+
+#[tokio::test]
+async fn simulcast_calendar() {
+    let crunchy = SESSION.get().await.unwrap();
+    let simulcast_season = crunchy
+        .simulcast_seasons(Locale::en_US)
+        .await
+        .unwrap()
+        .remove(0);
+
+    let start = Utc::now() - Duration::days(7);
+    let calendar = crunchy
+        .simulcast_calendar(&simulcast_season, start, start + Duration::days(7))
+        .await
+        .unwrap();
+    assert!(calendar.to_ics().starts_with("BEGIN:VCALENDAR\r\n"))
+}
+
+#[tokio::test]
+async fn watchlist_calendar() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let start = Utc::now();
+    assert_result!(
+        crunchy
+            .watchlist_calendar(start, start + Duration::days(7))
+            .await
+    )
+}
@@ -1,4 +1,5 @@
 use crate::utils::SESSION;
+use crunchyroll_rs::categories::SubCategoryInformation;
 
 mod utils;
 
@@ -7,3 +8,37 @@ async fn categories() {
     let crunchy = SESSION.get().await.unwrap();
     assert_result!(crunchy.categories().await)
 }
+
+#[tokio::test]
+async fn category_tree() {
+    let crunchy = SESSION.get().await.unwrap();
+    assert_result!(crunchy.category_tree().await)
+}
+
+#[test]
+fn sub_category_images() {
+    let sub_category: SubCategoryInformation = serde_json::from_value(serde_json::json!({
+        "id": "isekai",
+        "slug": "isekai",
+        "parent_category": "fantasy",
+        "tenant_category": "isekai",
+        "images": {
+            "background": [{
+                "source": "https://static.crunchyroll.com/isekai.png",
+                "type": "background",
+                "height": 1080,
+                "width": 1920
+            }],
+            "low": []
+        },
+        "localization": {
+            "title": "Isekai",
+            "description": "Another world",
+            "locale": "en-US"
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(sub_category.images.background[0].width, 1920);
+    assert_eq!(sub_category.localization.description, "Another world")
+}
@@ -0,0 +1,40 @@
+use crate::utils::SESSION;
+use crunchyroll_rs::crawler::CallbackSink;
+use crunchyroll_rs::search::BrowseOptions;
+use crunchyroll_rs::Locale;
+use std::collections::HashSet;
+
+mod utils;
+
+#[tokio::test]
+async fn crawler_resume() {
+    let crunchy = SESSION.get().await.unwrap();
+    let simulcast_season = crunchy
+        .simulcast_seasons(Locale::en_US)
+        .await
+        .unwrap()
+        .remove(0);
+    let checkpoint = std::env::temp_dir().join("crunchyroll-rs-crawler-checkpoint.json");
+    let _ = std::fs::remove_file(&checkpoint);
+
+    let crawler = crunchy
+        .crawler()
+        .browse_options(BrowseOptions::default().simulcast_season(simulcast_season.id))
+        .checkpoint(&checkpoint)
+        .originals_only(true);
+
+    let mut ids = HashSet::new();
+    let mut sink = CallbackSink::new(|media| {
+        assert!(ids.insert(media.id().to_string()), "media written twice");
+        Ok(())
+    });
+    let stats = crawler.run(&mut sink).await.unwrap();
+    assert!(stats.series + stats.movie_listings > 0);
+
+    // everything is marked as completed in the checkpoint, so nothing should be crawled again
+    let mut sink = CallbackSink::new(|_| panic!("media crawled again"));
+    let resumed = crawler.run(&mut sink).await.unwrap();
+    assert_eq!(resumed.skipped, stats.series + stats.movie_listings);
+
+    let _ = std::fs::remove_file(&checkpoint);
+}
@@ -1,5 +1,6 @@
 use crate::utils::Store;
 use crate::utils::SESSION;
+use crunchyroll_rs::error::CrunchyrollError;
 use crunchyroll_rs::Episode;
 
 mod utils;
@@ -79,3 +80,88 @@ async fn episode_versions() {
 
     assert_result!(episode.versions().await)
 }
+
+#[tokio::test]
+async fn episodes_from_ids() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    for episode in crunchy
+        .media_from_ids::<Episode>(&["GRDKJZ81Y", "G6QW40DE6"])
+        .await
+    {
+        assert_result!(episode)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn episode_serde_round_trip() {
+    let crunchy = SESSION.get().await.unwrap();
+    let episode = START_EPISODE.get().await.unwrap();
+
+    let serialized = serde_json::to_value(episode).unwrap();
+    let deserialized: Episode = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serialized, serde_json::to_value(&deserialized).unwrap());
+
+    let attached = crunchy.attach(deserialized).await;
+    assert_result!(attached.streams().await)
+}
+
+#[tokio::test]
+async fn episode_detach_attach() {
+    let crunchy = SESSION.get().await.unwrap();
+    let episode = START_EPISODE.get().await.unwrap();
+
+    let detached = episode.clone().detach();
+    assert_eq!(detached.id, episode.id);
+
+    let attached = detached.attach(crunchy).await;
+    assert_result!(attached.streams().await)
+}
+
+#[tokio::test]
+async fn episode_detached_request() {
+    let detached = Episode::default().detach();
+
+    assert!(matches!(
+        detached.streams().await,
+        Err(CrunchyrollError::Input(_))
+    ))
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn episode_detached_skip_events() {
+    let detached = Episode::default().detach();
+
+    assert!(matches!(
+        detached.skip_events().await,
+        Err(CrunchyrollError::Input(_))
+    ));
+    assert!(matches!(
+        detached.intro().await,
+        Err(CrunchyrollError::Input(_))
+    ))
+}
+
+#[tokio::test]
+async fn episode_skip_events() {
+    let episode = START_EPISODE.get().await.unwrap();
+
+    assert_result!(episode.skip_events().await)
+}
+
+#[tokio::test]
+async fn episodes_playheads() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    assert_result!(crunchy.playheads(&["GRDKJZ81Y", "G6QW40DE6"]).await)
+}
+
+#[tokio::test]
+async fn episode_mark_watched() {
+    let episode = END_EPISODE.get().await.unwrap();
+
+    assert_result!(episode.mark_watched().await);
+    assert_result!(episode.mark_unwatched().await)
+}
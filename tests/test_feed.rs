@@ -1,5 +1,5 @@
 use crate::utils::{Store, SESSION};
-use crunchyroll_rs::feed::HomeFeed;
+use crunchyroll_rs::feed::{HomeFeed, NewsExportFormat, NewsFeed};
 use futures_util::StreamExt;
 
 mod utils;
@@ -42,3 +42,72 @@ async fn recommendations() {
         .await
         .unwrap())
 }
+
+#[tokio::test]
+async fn home_feed_resolved() {
+    assert_result!(SESSION.get().await.unwrap().home_feed_resolved().await)
+}
+
+#[tokio::test]
+async fn news_article() {
+    let news = SESSION
+        .get()
+        .await
+        .unwrap()
+        .news_feed()
+        .top_news
+        .next()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_result!(news.article().await)
+}
+
+#[test]
+fn news_export() {
+    let mut news = NewsFeed::default();
+    news.title = "Dubs & more".to_string();
+    news.news_link = "https://www.crunchyroll.com/news/latest/2023/3/1/dubs".to_string();
+    news.creator = "Crunchyroll News".to_string();
+
+    let rss = NewsExportFormat::Rss.export(&[news.clone()]);
+    assert!(rss.contains("<title>Dubs &amp; more</title>"));
+    assert!(rss.contains("xmlns:dc=\"http://purl.org/dc/elements/1.1/\""));
+    assert!(rss.contains("<dc:creator>Crunchyroll News</dc:creator>"));
+    // no image, no enclosure
+    assert!(!rss.contains("<enclosure"));
+
+    let mut with_image = news.clone();
+    with_image.image_link = "https://a.storyblok.com/f/1/dubs.png?v=1".to_string();
+    let rss = NewsExportFormat::Rss.export(&[with_image]);
+    assert!(rss.contains("type=\"image/png\""));
+
+    let atom = NewsExportFormat::Atom.export(&[news]);
+    assert!(atom.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(atom.contains("<id>https://www.crunchyroll.com/news/latest/2023/3/1/dubs</id>"))
+}
+
+#[test]
+fn news_slug() {
+    let mut news = NewsFeed::default();
+
+    news.news_link = "https://www.crunchyroll.com/news/latest/2023/3/1/dubs/".to_string();
+    assert_eq!(news.slug(), Some("news/latest/2023/3/1/dubs"));
+
+    news.news_link = "https://www.crunchyroll.com/de/news/latest/2023/3/1/dubs".to_string();
+    assert_eq!(news.slug(), Some("news/latest/2023/3/1/dubs"));
+
+    news.news_link =
+        "https://www.crunchyroll.com/pt-br/news/announcements/2023/3/1/dubs?utm=feed".to_string();
+    assert_eq!(news.slug(), Some("news/announcements/2023/3/1/dubs"));
+
+    news.news_link = "https://example.com/news/latest/2023/3/1/dubs".to_string();
+    assert_eq!(news.slug(), None)
+}
+
+#[tokio::test]
+async fn news_feed_export() {
+    let latest_news = SESSION.get().await.unwrap().news_feed().latest_news;
+    let atom = latest_news.export(NewsExportFormat::Atom, 10).await;
+    assert_result!(atom)
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "index")]
+
+use crunchyroll_rs::index::{CatalogIndex, IndexDocument, IndexMediaType, IndexSearchOptions};
+use crunchyroll_rs::Locale;
+
+fn document(id: &str, title: &str, audio_locales: Vec<Locale>, is_mature: bool) -> IndexDocument {
+    IndexDocument {
+        id: id.to_string(),
+        media_type: IndexMediaType::Series,
+        titles: vec![title.to_string()],
+        descriptions: vec![],
+        tags: vec![],
+        audio_locales,
+        subtitle_locales: vec![],
+        is_mature,
+        is_premium_only: false,
+    }
+}
+
+fn index() -> CatalogIndex {
+    let mut index = CatalogIndex::new();
+    index.insert(document(
+        "1",
+        "Darling in the Franxx",
+        vec![Locale::ja_JP, Locale::en_US],
+        false,
+    ));
+    index.insert(document("2", "Attack on Titan", vec![Locale::ja_JP], true));
+    index.insert(document("3", "Spy x Family", vec![Locale::ja_JP], false));
+    index
+}
+
+#[test]
+fn index_search() {
+    let index = index();
+    let options = IndexSearchOptions::default();
+
+    assert_eq!(index.search("titan", &options)[0].document.id, "2");
+    // prefix
+    assert_eq!(index.search("dar", &options)[0].document.id, "1");
+    // typo
+    assert_eq!(index.search("famliy", &options)[0].document.id, "3");
+    assert!(index.search("naruto", &options).is_empty())
+}
+
+#[test]
+fn index_search_filter() {
+    let index = index();
+
+    let options = IndexSearchOptions::default().include_mature(false);
+    assert!(index.search("titan", &options).is_empty());
+    let options = IndexSearchOptions::default().audio_locale(Locale::en_US);
+    assert!(index.search("family", &options).is_empty())
+}
+
+#[test]
+fn index_merge() {
+    let mut index = index();
+    index.insert(document("3", "Spion x Familie", vec![Locale::de_DE], false));
+
+    assert_eq!(index.len(), 3);
+    let options = IndexSearchOptions::default();
+    assert_eq!(index.search("spion", &options)[0].document.id, "3");
+    assert_eq!(index.search("spy", &options)[0].document.id, "3")
+}
+
+#[test]
+fn index_save_load() {
+    let index = index();
+    let path = std::env::temp_dir().join("crunchyroll-rs-test-index.json");
+
+    index.save(&path).unwrap();
+    let loaded = CatalogIndex::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.documents(), index.documents());
+    assert_eq!(
+        loaded.search("titan", &IndexSearchOptions::default())[0]
+            .document
+            .id,
+        "2"
+    )
+}
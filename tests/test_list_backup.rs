@@ -0,0 +1,180 @@
+use crate::utils::SESSION;
+use crunchyroll_rs::list::{BackupCrunchylist, BackupEntry, ListBackup};
+use crunchyroll_rs::media::MediaType;
+
+mod utils;
+
+fn backup() -> ListBackup {
+    ListBackup {
+        watchlist: vec![
+            BackupEntry {
+                id: "GY8VEQ95Y".to_string(),
+                title: "DARLING in the FRANXX".to_string(),
+                media_type: MediaType::Series,
+                is_favorite: true,
+                fully_watched: false,
+                in_progress: true,
+            },
+            BackupEntry {
+                id: "G25FVD45Q".to_string(),
+                title: "Movie, with \"quotes\" ]]> & more".to_string(),
+                media_type: MediaType::Movie,
+                is_favorite: false,
+                fully_watched: true,
+                in_progress: false,
+            },
+        ],
+        crunchylists: vec![BackupCrunchylist {
+            title: "Favorites, maybe".to_string(),
+            entries: vec![BackupEntry {
+                id: "GY8VEQ95Y".to_string(),
+                title: "DARLING in the FRANXX".to_string(),
+                media_type: MediaType::Series,
+                ..Default::default()
+            }],
+        }],
+    }
+}
+
+#[test]
+fn list_backup_json() {
+    let backup = backup();
+    let json = backup.to_json().unwrap();
+    assert_eq!(ListBackup::from_json(json).unwrap(), backup)
+}
+
+#[test]
+fn list_backup_csv() {
+    let backup = backup();
+    let csv = backup.to_csv();
+    assert_eq!(ListBackup::from_csv(csv).unwrap(), backup)
+}
+
+#[test]
+fn list_backup_mal_xml() {
+    let backup = backup();
+    let xml = backup.to_mal_xml();
+    let imported = ListBackup::from_mal_xml(xml).unwrap();
+    // myanimelist has no custom lists
+    assert!(imported.crunchylists.is_empty());
+    assert_eq!(imported.watchlist, backup.watchlist)
+}
+
+#[test]
+fn list_backup_csv_without_in_progress() {
+    let csv = "list_type,list_title,id,title,media_type,is_favorite,fully_watched\r\n\
+               watchlist,,GY8VEQ95Y,DARLING in the FRANXX,series,true,false\r\n";
+    let backup = ListBackup::from_csv(csv).unwrap();
+    assert_eq!(backup.watchlist.len(), 1);
+    assert!(!backup.watchlist[0].in_progress)
+}
+
+#[test]
+fn list_backup_mal_export() {
+    // shortened export of myanimelist itself
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<myanimelist>
+	<myinfo>
+		<user_id>1234567</user_id>
+		<user_name>example</user_name>
+		<user_export_type>1</user_export_type>
+		<user_total_anime>3</user_total_anime>
+		<user_total_watching>1</user_total_watching>
+		<user_total_completed>1</user_total_completed>
+		<user_total_onhold>0</user_total_onhold>
+		<user_total_dropped>0</user_total_dropped>
+		<user_total_plantowatch>1</user_total_plantowatch>
+	</myinfo>
+	<anime>
+		<series_animedb_id>35849</series_animedb_id>
+		<series_title><![CDATA[Darling in the FranXX]]></series_title>
+		<series_type>TV</series_type>
+		<series_episodes>24</series_episodes>
+		<my_id>0</my_id>
+		<my_watched_episodes>24</my_watched_episodes>
+		<my_start_date>2018-01-13</my_start_date>
+		<my_finish_date>2018-07-07</my_finish_date>
+		<my_score>8</my_score>
+		<my_status>Completed</my_status>
+		<my_comments><![CDATA[]]></my_comments>
+		<my_tags><![CDATA[mecha, favorite]]></my_tags>
+		<update_on_import>0</update_on_import>
+	</anime>
+	<anime>
+		<series_animedb_id>52991</series_animedb_id>
+		<series_title><![CDATA[Sousou no Frieren]]></series_title>
+		<series_type>TV</series_type>
+		<series_episodes>28</series_episodes>
+		<my_id>0</my_id>
+		<my_watched_episodes>10</my_watched_episodes>
+		<my_score>0</my_score>
+		<my_status>Watching</my_status>
+		<my_comments><![CDATA[Rewatch with friends]]></my_comments>
+		<my_tags><![CDATA[]]></my_tags>
+		<update_on_import>0</update_on_import>
+	</anime>
+	<anime>
+		<series_animedb_id>28851</series_animedb_id>
+		<series_title><![CDATA[Koe no Katachi]]></series_title>
+		<series_type>Movie</series_type>
+		<series_episodes>1</series_episodes>
+		<my_id>0</my_id>
+		<my_watched_episodes>0</my_watched_episodes>
+		<my_score>0</my_score>
+		<my_status>Plan to Watch</my_status>
+		<my_comments></my_comments>
+		<my_tags></my_tags>
+		<update_on_import>0</update_on_import>
+	</anime>
+</myanimelist>"#;
+
+    let backup = ListBackup::from_mal_xml(xml).unwrap();
+    assert_eq!(
+        backup.watchlist,
+        vec![
+            BackupEntry {
+                id: "".to_string(),
+                title: "Darling in the FranXX".to_string(),
+                media_type: MediaType::Series,
+                is_favorite: true,
+                fully_watched: true,
+                in_progress: false,
+            },
+            BackupEntry {
+                // comments which aren't crunchyroll ids are ignored
+                id: "".to_string(),
+                title: "Sousou no Frieren".to_string(),
+                media_type: MediaType::Series,
+                is_favorite: false,
+                fully_watched: false,
+                in_progress: true,
+            },
+            BackupEntry {
+                id: "".to_string(),
+                title: "Koe no Katachi".to_string(),
+                media_type: MediaType::Movie,
+                is_favorite: false,
+                fully_watched: false,
+                in_progress: false,
+            },
+        ]
+    )
+}
+
+#[tokio::test]
+async fn import_lists() {
+    let crunchy = SESSION.get().await.unwrap();
+    // importing the current lists doesn't change anything
+    let backup = crunchy.export_lists().await.unwrap();
+    let report = crunchy.import_lists(&backup).await;
+    assert_result!(report);
+    let report = report.unwrap();
+    assert_eq!(report.watchlist_added, 0);
+    assert_eq!(report.crunchylists_created, 0)
+}
+
+#[tokio::test]
+async fn export_lists() {
+    let crunchy = SESSION.get().await.unwrap();
+    assert_result!(crunchy.export_lists().await)
+}
@@ -0,0 +1,51 @@
+use crunchyroll_rs::locale::{LocaleMatch, LocalePreference};
+use crunchyroll_rs::Locale;
+
+#[test]
+fn locale_from_bcp47() {
+    assert_eq!(Locale::from("en-US".to_string()), Locale::en_US);
+    assert_eq!(Locale::from_bcp47("en_us"), Locale::en_US);
+    assert_eq!(Locale::from_bcp47("EN-Latn-US"), Locale::en_US);
+    assert_eq!(Locale::from_bcp47("es-419"), Locale::es_419);
+    assert_eq!(Locale::from_bcp47("de"), Locale::de_DE);
+    assert_eq!(
+        Locale::from_bcp47("de-at"),
+        Locale::Custom("de-AT".to_string())
+    );
+}
+
+#[test]
+fn locale_matches() {
+    assert_eq!(Locale::de_DE.matches(&Locale::de_DE), LocaleMatch::Exact);
+    assert_eq!(Locale::es_LA.matches(&Locale::es_419), LocaleMatch::Exact);
+    assert_eq!(
+        Locale::es_ES.matches(&Locale::es_419),
+        LocaleMatch::Language
+    );
+    assert_eq!(Locale::de_DE.matches(&Locale::en_US), LocaleMatch::NoMatch);
+}
+
+#[test]
+fn locale_display_name() {
+    assert_eq!(Locale::de_DE.to_human_readable(), "German");
+    assert_eq!(Locale::de_DE.display_name(&Locale::de_DE), "Deutsch");
+    assert_eq!(Locale::ja_JP.display_name(&Locale::fr_FR), "Japonais");
+    assert_eq!(Locale::ja_JP.display_name(&Locale::ru_RU), "Japanese");
+}
+
+#[test]
+fn locale_preference() {
+    let preference = LocalePreference::new(vec![Locale::de_DE])
+        .then(Locale::en_US)
+        .then(Locale::ja_JP);
+
+    assert_eq!(
+        preference.best_match(&[Locale::ja_JP, Locale::en_US]),
+        Some(&Locale::en_US)
+    );
+    assert_eq!(
+        preference.best_match(&[Locale::en_US, Locale::Custom("de-AT".to_string())]),
+        Some(&Locale::Custom("de-AT".to_string()))
+    );
+    assert_eq!(preference.best_match(&[Locale::fr_FR]), None)
+}
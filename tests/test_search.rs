@@ -1,5 +1,8 @@
 use crate::utils::SESSION;
-use crunchyroll_rs::Locale;
+use crunchyroll_rs::search::{
+    BrowseOptions, BrowseSortType, SearchOptions, SearchType, SimulcastQuarter, SimulcastSeason,
+};
+use crunchyroll_rs::{Locale, MediaCollection};
 use futures_util::StreamExt;
 
 mod utils;
@@ -11,6 +14,20 @@ async fn by_browse() {
     assert_result!(crunchy.browse(Default::default()).next().await.unwrap());
 }
 
+#[tokio::test]
+async fn by_browse_filtered() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let options = BrowseOptions::default()
+        .sort(BrowseSortType::Rating)
+        .audio_locale(Locale::de_DE)
+        .launch_year_from(2015)
+        .launch_year_to(2020)
+        .start_letters(('a'..='f').collect());
+    let mut results = crunchy.browse(options);
+    assert_result!(results.next().await.unwrap())
+}
+
 #[tokio::test]
 async fn by_query() {
     let crunchy = SESSION.get().await.unwrap();
@@ -32,3 +49,88 @@ async fn simulcast_seasons() {
 
     assert_result!(crunchy.simulcast_seasons(Locale::en_US).await)
 }
+
+#[test]
+fn simulcast_season_id() {
+    let mut season = SimulcastSeason::default();
+    season.id = "fall-2023".to_string();
+    assert_eq!(
+        season.quarter_and_year(),
+        Some((SimulcastQuarter::Fall, 2023))
+    );
+
+    season.id = "not-a-season".to_string();
+    assert_eq!(season.quarter_and_year(), None)
+}
+
+#[tokio::test]
+async fn current_simulcast_season() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let season = crunchy.current_simulcast_season().await;
+    assert_result!(season);
+    assert!(season.unwrap().unwrap().quarter_and_year().is_some())
+}
+
+#[tokio::test]
+async fn simulcast_season_lineup() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let season = crunchy.current_simulcast_season().await.unwrap().unwrap();
+    assert_result!(season.lineup().await)
+}
+
+#[tokio::test]
+async fn by_music_query() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let mut result = crunchy.query("lisa");
+    assert_result!(result.music.next().await.unwrap());
+    assert_result!(result.artist.next().await.unwrap())
+}
+
+#[tokio::test]
+async fn music_browse() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    assert_result!(crunchy.music_genres().await);
+    assert_result!(crunchy
+        .browse_music_videos(Default::default())
+        .next()
+        .await
+        .unwrap());
+    assert_result!(crunchy.artists().next().await.unwrap())
+}
+
+#[tokio::test]
+async fn by_search() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let options = SearchOptions::default()
+        .types(vec![SearchType::Series, SearchType::Episode])
+        .page_size(10);
+    let mut results = crunchy.search("darling", options);
+    assert_result!(results.next().await.unwrap())
+}
+
+#[tokio::test]
+async fn search_suggestions() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let suggestions = crunchy.search_suggestions("darl", 5).await;
+    assert_result!(suggestions);
+    let suggestions = suggestions.unwrap();
+    assert!(suggestions.len() <= 5);
+    assert!(suggestions
+        .iter()
+        .all(|s| !s.id.is_empty() && s.id == s.media.id()))
+}
+
+#[tokio::test]
+async fn search_artists() {
+    let crunchy = SESSION.get().await.unwrap();
+    let options = SearchOptions::default().types(vec![SearchType::Artist]);
+
+    let artist = crunchy.search("aimer", options).next().await.unwrap();
+    assert!(matches!(artist, Ok(MediaCollection::Artist(_))))
+}
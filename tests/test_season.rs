@@ -1,5 +1,6 @@
 use crate::utils::Store;
 use crate::utils::SESSION;
+use crunchyroll_rs::media::MediaHierarchy;
 use crunchyroll_rs::Season;
 
 mod utils;
@@ -20,3 +21,23 @@ async fn season_from_id() {
 async fn season_episodes() {
     assert_result!(SEASON.get().await.unwrap().episodes().await)
 }
+
+#[tokio::test]
+async fn season_series() {
+    assert_result!(SEASON.get().await.unwrap().series().await)
+}
+
+#[tokio::test]
+async fn season_hierarchy() {
+    let season = SEASON.get().await.unwrap();
+
+    let parent = season.parent().await.unwrap().unwrap();
+    assert_eq!(parent.id, season.series_id);
+    assert_eq!(season.root().await.unwrap().id, season.series_id);
+    assert_result!(season.children().await)
+}
+
+#[tokio::test]
+async fn season_playheads() {
+    assert_result!(SEASON.get().await.unwrap().playheads().await)
+}
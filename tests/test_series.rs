@@ -27,3 +27,17 @@ async fn series_seasons() {
 async fn series_similar() {
     assert_result!(SERIES.get().await.unwrap().similar().next().await.unwrap())
 }
+
+#[tokio::test]
+async fn series_version_matrix() {
+    assert_result!(SERIES.get().await.unwrap().version_matrix().await)
+}
+
+#[tokio::test]
+async fn series_poster_download() {
+    let crunchy = SESSION.get().await.unwrap();
+    let series = SERIES.get().await.unwrap();
+
+    let poster = series.images.best_for(480, 720).unwrap();
+    assert_result!(poster.download(crunchy).await)
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "sqlite")]
+
+use crate::utils::SESSION;
+use crunchyroll_rs::crawler::CrawlSink;
+use crunchyroll_rs::sqlite::SqliteExporter;
+use crunchyroll_rs::{Episode, Locale, MediaCollection, MusicVideo};
+use rusqlite::Connection;
+
+mod utils;
+
+fn count(exporter: &SqliteExporter, table: &str) -> u32 {
+    exporter
+        .connection()
+        .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+}
+
+#[test]
+fn sqlite_upsert() {
+    let mut exporter =
+        SqliteExporter::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+    let mut episode = Episode::default();
+    episode.id = "episode".to_string();
+    episode.title = "Old title".to_string();
+    episode.subtitle_locales = vec![Locale::en_US, Locale::de_DE];
+    exporter
+        .export(&MediaCollection::from(episode.clone()))
+        .unwrap();
+
+    episode.title = "New title".to_string();
+    episode.subtitle_locales = vec![Locale::en_US];
+    exporter.export(&MediaCollection::from(episode)).unwrap();
+
+    assert_eq!(count(&exporter, "episodes"), 1);
+    assert_eq!(count(&exporter, "locales"), 1);
+    let title: String = exporter
+        .connection()
+        .query_row(
+            "SELECT title FROM episodes WHERE id = 'episode'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(title, "New title")
+}
+
+#[test]
+fn sqlite_export_all_count() {
+    let mut exporter =
+        SqliteExporter::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+    let mut episode = Episode::default();
+    episode.id = "episode".to_string();
+    let mut music_video = MusicVideo::default();
+    music_video.id = "music_video".to_string();
+    let media = [
+        MediaCollection::from(episode),
+        MediaCollection::from(music_video),
+    ];
+
+    // music videos aren't exported and not counted
+    assert_eq!(exporter.export_all(&media).unwrap(), 1)
+}
+
+#[tokio::test]
+async fn sqlite_sink_flush() {
+    let mut exporter =
+        SqliteExporter::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+    for id in ["first", "second"] {
+        let mut episode = Episode::default();
+        episode.id = id.to_string();
+        exporter
+            .write(MediaCollection::from(episode))
+            .await
+            .unwrap()
+    }
+    // written media is only exported when flushing
+    assert_eq!(count(&exporter, "episodes"), 0);
+
+    exporter.flush().await.unwrap();
+    assert_eq!(count(&exporter, "episodes"), 2)
+}
+
+#[tokio::test]
+async fn sqlite_export_ids() {
+    let crunchy = SESSION.get().await.unwrap();
+    let mut exporter =
+        SqliteExporter::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+    let report = exporter
+        .export_ids(crunchy, &["GY8VEQ95Y", "INVALIDID"])
+        .await
+        .unwrap();
+
+    assert!(report.exported > 1);
+    assert_eq!(report.unknown, vec!["INVALIDID".to_string()]);
+    assert!(report.failed.is_empty());
+    assert_eq!(count(&exporter, "series"), 1);
+    assert!(count(&exporter, "episodes") > 0)
+}
@@ -18,3 +18,9 @@ async fn clear_watch_history() {
     let crunchy = SESSION.get().await.unwrap();
     assert_result!(crunchy.clear_watch_history().await)
 }
+
+#[tokio::test]
+async fn continue_watching() {
+    let crunchy = SESSION.get().await.unwrap();
+    assert_result!(crunchy.continue_watching(5).await)
+}
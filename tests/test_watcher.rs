@@ -0,0 +1,152 @@
+use crate::utils::SESSION;
+use crunchyroll_rs::watcher::{
+    EpisodeSnapshot, MemorySnapshotStore, SeasonSnapshot, SeriesSnapshot, SnapshotStore,
+    WatchEvent, WatchTarget,
+};
+use crunchyroll_rs::Locale;
+
+mod utils;
+
+fn episode(id: &str, is_premium_only: bool) -> EpisodeSnapshot {
+    EpisodeSnapshot {
+        id: id.to_string(),
+        season_id: "season".to_string(),
+        is_premium_only,
+        ..Default::default()
+    }
+}
+
+fn snapshot(seasons: Vec<SeasonSnapshot>, audio_locales: Vec<Locale>) -> SeriesSnapshot {
+    SeriesSnapshot {
+        id: "series".to_string(),
+        audio_locales,
+        seasons,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn watcher_diff_unchanged() {
+    let old = snapshot(
+        vec![SeasonSnapshot {
+            id: "season".to_string(),
+            episodes: vec![episode("1", false)],
+            ..Default::default()
+        }],
+        vec![Locale::ja_JP],
+    );
+
+    assert!(old.clone().diff(&old).is_empty())
+}
+
+#[test]
+fn watcher_diff_episodes() {
+    let old = snapshot(
+        vec![SeasonSnapshot {
+            id: "season".to_string(),
+            episodes: vec![episode("1", true), episode("2", false)],
+            ..Default::default()
+        }],
+        vec![Locale::ja_JP],
+    );
+    let new = snapshot(
+        vec![SeasonSnapshot {
+            id: "season".to_string(),
+            episodes: vec![episode("1", false), episode("3", true)],
+            ..Default::default()
+        }],
+        vec![Locale::ja_JP],
+    );
+
+    assert_eq!(
+        new.diff(&old),
+        vec![
+            WatchEvent::NowFree {
+                series_id: "series".to_string(),
+                episode: episode("1", false)
+            },
+            WatchEvent::NewEpisode {
+                series_id: "series".to_string(),
+                episode: episode("3", true)
+            },
+            WatchEvent::EpisodeRemoved {
+                series_id: "series".to_string(),
+                episode: episode("2", false)
+            }
+        ]
+    )
+}
+
+#[test]
+fn watcher_diff_seasons() {
+    let old_season = SeasonSnapshot {
+        id: "season".to_string(),
+        ..Default::default()
+    };
+    let dub_season = SeasonSnapshot {
+        id: "dub".to_string(),
+        audio_locales: vec![Locale::en_US],
+        ..Default::default()
+    };
+    let old = snapshot(vec![old_season.clone()], vec![Locale::ja_JP]);
+    let new = snapshot(vec![dub_season.clone()], vec![Locale::ja_JP, Locale::en_US]);
+
+    assert_eq!(
+        new.diff(&old),
+        vec![
+            WatchEvent::NewDubLocale {
+                series_id: "series".to_string(),
+                locale: Locale::en_US
+            },
+            WatchEvent::NewSeason {
+                series_id: "series".to_string(),
+                season: dub_season
+            },
+            WatchEvent::SeasonRemoved {
+                series_id: "series".to_string(),
+                season: old_season
+            }
+        ]
+    )
+}
+
+#[tokio::test]
+async fn watcher_poll() {
+    let crunchy = SESSION.get().await.unwrap();
+    let watcher = crunchy.watcher(
+        WatchTarget::Series(vec!["GY8VEQ95Y".to_string()]),
+        MemorySnapshotStore::default(),
+    );
+
+    assert_result!(watcher.poll().await);
+    // nothing should've changed in the meantime
+    let result = watcher.poll().await.unwrap();
+    assert!(result.events.is_empty());
+    assert!(result.failed.is_empty())
+}
+
+#[tokio::test]
+async fn watcher_poll_removed() {
+    let crunchy = SESSION.get().await.unwrap();
+    let removed = SeriesSnapshot {
+        id: "GREMOVED00".to_string(),
+        ..Default::default()
+    };
+    let store = MemorySnapshotStore::default();
+    store.save(removed.clone()).await.unwrap();
+    let watcher = crunchy.watcher(
+        WatchTarget::Series(vec!["GREMOVED00".to_string(), "GY8VEQ95Y".to_string()]),
+        store,
+    );
+
+    let result = watcher.poll().await.unwrap();
+    assert!(result.failed.is_empty());
+    assert_eq!(
+        result.events,
+        vec![WatchEvent::SeriesRemoved {
+            series_id: "GREMOVED00".to_string(),
+            series: removed
+        }]
+    );
+    assert!(watcher.store().load("GREMOVED00").await.unwrap().is_none())
+}
@@ -1,6 +1,7 @@
 use crate::utils::{Store, SESSION};
 use crunchyroll_rs::list::WatchlistOptions;
 use crunchyroll_rs::Series;
+use futures_util::StreamExt;
 
 mod utils;
 
@@ -15,7 +16,22 @@ static SERIES: Store<Series> = Store::new(|| {
 #[tokio::test]
 async fn watchlist() {
     let crunchy = SESSION.get().await.unwrap();
-    assert_result!(crunchy.watchlist(WatchlistOptions::default()).await)
+    assert_result!(crunchy
+        .watchlist(WatchlistOptions::default())
+        .next()
+        .await
+        .unwrap())
+}
+
+#[tokio::test]
+async fn watchlist_filtered() {
+    let crunchy = SESSION.get().await.unwrap();
+
+    let mut watchlist = crunchy.watchlist(WatchlistOptions::default().fully_watched(false));
+    watchlist.page_size(5);
+    while let Some(entry) = watchlist.next().await {
+        assert!(!entry.unwrap().fully_watched)
+    }
 }
 
 #[tokio::test]